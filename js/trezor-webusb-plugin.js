@@ -1,4 +1,4 @@
-// type TrezorDeviceInfoDebug = {path: string, debug: boolean};
+// type TrezorDeviceInfoDebug = {path: string, debug: boolean, vendorId: number, productId: number};
 
 const T1HID_VENDOR = 0x534c;
 const TREZOR_DESCS = [
@@ -16,6 +16,8 @@ const INTERFACE_ID = 0;
 const ENDPOINT_ID = 1;
 const DEBUG_INTERFACE_ID = 1;
 const DEBUG_ENDPOINT_ID = 2;
+// How many empty packets in a row a read tolerates.
+const MAX_EMPTY_READS = 10;
 
 export class TrezorWebUsbPlugin {
     // boolean
@@ -109,7 +111,12 @@ export class TrezorWebUsbPlugin {
 
     // @return - Promise<Array<TrezorDeviceInfoDebug>>
     async enumerate() {
-        return (await this._listDevices()).map(info => ({path: info.path, debug: info.debug}));
+        return (await this._listDevices()).map(info => ({
+            path: info.path,
+            debug: info.debug,
+            vendorId: info.device.vendorId,
+            productId: info.device.productId,
+        }));
     }
 
     _findDeviceInfo(path) {
@@ -154,11 +161,14 @@ export class TrezorWebUsbPlugin {
                 await this.connect(path, debug, false);
             }
 
-            const res = await device.transferIn(endpoint, 64);
-            if (res.data.byteLength === 0) {
-                return this.receive(path, debug);
+            // The device may send empty packets, but not forever.
+            for (let attempt = 0; attempt < MAX_EMPTY_READS; attempt++) {
+                const res = await device.transferIn(endpoint, 64);
+                if (res.data.byteLength !== 0) {
+                    return res.data.buffer;
+                }
             }
-            return res.data.buffer;
+            throw new Error(`The device keeps sending empty packets.`);
             // return res.data.buffer.slice(1);
         } catch (e) {
             if (e.message === `Device unavailable.`) {
@@ -239,6 +249,22 @@ export class TrezorWebUsbPlugin {
         }
     }
 
+    // @callback - function, called when a device is connected or disconnected
+    listen(callback) {
+        this.unlisten();
+        this._listener = () => callback();
+        this.usb.addEventListener(`connect`, this._listener);
+        this.usb.addEventListener(`disconnect`, this._listener);
+    }
+
+    unlisten() {
+        if (this._listener != null) {
+            this.usb.removeEventListener(`connect`, this._listener);
+            this.usb.removeEventListener(`disconnect`, this._listener);
+            this._listener = null;
+        }
+    }
+
     // @return - Promise<void>
     async requestDevice() {
         // I am throwing away the resulting device, since it appears in enumeration anyway
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+use std::fs;
 use std::path::Path;
 
 const MESSAGES_PROTO: &str = "protos/messages.proto";
@@ -6,19 +8,257 @@ const MESSAGES_MANAGEMENT_PROTO: &str = "protos/messages-management.proto";
 
 const MESSAGES_BITCOIN_PROTO: &str = "protos/messages-bitcoin.proto";
 const MESSAGES_TEZOS_PROTO: &str = "protos/messages-tezos.proto";
+const MESSAGES_DEBUG_PROTO: &str = "protos/messages-debug.proto";
 
-use protoc_rust::Customize;
+const PROTOS: &[&str] = &[
+    MESSAGES_PROTO,
+    MESSAGES_COMMON_PROTO,
+    MESSAGES_MANAGEMENT_PROTO,
+    MESSAGES_BITCOIN_PROTO,
+    MESSAGES_TEZOS_PROTO,
+    MESSAGES_DEBUG_PROTO,
+];
+
+/// The TrezorMessage impls and the message registry, included by `src/messages.rs`.
+const MESSAGES_GENERATED: &str = "messages_generated.rs";
 
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=protos");
+
     // prost_build::compile_protos(
     //     &[MESSAGES_PROTO, MESSAGES_COMMON_PROTO, MESSAGES_MANAGEMENT_PROTO, MESSAGES_BITCOIN_PROTO, MESSAGES_TEZOS_PROTO],
     //     &["protos"],
     // ).unwrap();
 
-    protoc_rust::Codegen::new()
-        .out_dir("src/protos")
-        .inputs(&[MESSAGES_PROTO, MESSAGES_COMMON_PROTO, MESSAGES_MANAGEMENT_PROTO, MESSAGES_BITCOIN_PROTO, MESSAGES_TEZOS_PROTO])
-        .include("protos")
-        .run()
-        .expect("protoc");
+    // The checked-in code is generated with protoc, the `pure-codegen` feature parses
+    // the protos in Rust and generates the code into `OUT_DIR` instead.
+    #[cfg(not(feature = "pure-codegen"))]
+    {
+        protoc_rust::Codegen::new()
+            .out_dir("src/protos")
+            .inputs(PROTOS)
+            .include("protos")
+            .customize(protoc_rust::Customize {
+                serde_derive: Some(true),
+                ..Default::default()
+            })
+            .run()
+            .expect("protoc");
+
+        write_if_changed(
+            &Path::new("src").join(MESSAGES_GENERATED),
+            &generate_messages(PROTOS),
+        );
+    }
+
+    #[cfg(feature = "pure-codegen")]
+    {
+        let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR"));
+        let protos_dir = out_dir.join("protos");
+        fs::create_dir_all(&protos_dir).expect("create the protos directory");
+        let parsed = protobuf_parse::Parser::new()
+            .pure()
+            .include("protos")
+            .inputs(PROTOS)
+            .parse_and_typecheck()
+            .expect("parse the protos");
+        // The parser comes from rust-protobuf 3 and the code generator from rust-protobuf 2,
+        // so the descriptors go from one to the other in the wire format.
+        let file_descriptors: Vec<protobuf::descriptor::FileDescriptorProto> = parsed
+            .file_descriptors
+            .iter()
+            .map(|descriptor| {
+                let bytes = protobuf3::Message::write_to_bytes(descriptor).expect("encode");
+                protobuf::Message::parse_from_bytes(&bytes).expect("decode")
+            })
+            .collect();
+        let inputs: Vec<String> = parsed
+            .relative_paths
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let customize = protobuf_codegen::Customize {
+            serde_derive: Some(true),
+            // `src/protos/mod.rs` includes the generated `mod.rs`.
+            gen_mod_rs: Some(true),
+            ..Default::default()
+        };
+        protobuf_codegen::gen_and_write(&file_descriptors, &inputs, &protos_dir, &customize)
+            .expect("generate the messages");
+
+        write_if_changed(
+            &out_dir.join(MESSAGES_GENERATED),
+            &generate_messages(PROTOS),
+        );
+    }
+}
+
+/// Only touch the file when it changes, so cargo doesn't rebuild for nothing.
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        fs::write(path, contents).expect("write the generated code");
+    }
+}
+
+/// The options of the `MessageType` entries, with the marker traits they map to.
+const WIRE_MARKERS: &[(&str, &str)] = &[
+    ("wire_in", "HostToDevice"),
+    ("wire_out", "DeviceToHost"),
+    ("wire_debug_in", "DebugOnly"),
+    ("wire_debug_out", "DebugOnly"),
+    ("wire_bootloader", "BootloaderOnly"),
+];
+
+/// A `MessageType` entry.
+struct MessageType {
+    id: u32,
+    /// The options set to true, f.e. `wire_in`.
+    options: Vec<String>,
+}
+
+impl MessageType {
+    fn has_option(&self, option: &str) -> bool {
+        self.options.iter().any(|o| o == option)
+    }
+
+    /// The marker traits of the messages of this type.
+    fn markers(&self) -> Vec<&'static str> {
+        let mut markers: Vec<_> = WIRE_MARKERS
+            .iter()
+            .filter(|(option, _)| self.has_option(option))
+            .map(|&(_, marker)| marker)
+            .collect();
+        markers.dedup();
+        markers
+    }
+}
+
+/// Generate a `trezor_message_impl!` for every message named like a `MessageType_*`
+/// entry, or which overrides its wire type, the registry of the former, and the list
+/// of the types available on the Bitcoin-only firmware.
+fn generate_messages(protos: &[&str]) -> String {
+    let mut message_types = BTreeMap::new();
+    let mut messages = Vec::new();
+    for proto in protos {
+        let source = fs::read_to_string(Path::new(proto)).expect("read proto");
+        parse_proto(&source, &mut message_types, &mut messages);
+    }
+    let type_names: BTreeMap<u32, &str> = message_types
+        .iter()
+        .map(|(name, message_type)| (message_type.id, name.as_str()))
+        .collect();
+
+    let mut impls = String::new();
+    let mut registry = String::new();
+    for (message, wire_type) in &messages {
+        let message_type = match wire_type {
+            Some(id) => type_names.get(id).map(|name| name.to_string()),
+            None => message_types.get(message).map(|_| message.clone()),
+        };
+        if let Some(message_type) = message_type {
+            let markers: String = message_types[&message_type]
+                .markers()
+                .iter()
+                .map(|marker| format!(", {}", marker))
+                .collect();
+            impls += &format!(
+                "trezor_message_impl!({}, MessageType_{}{});\n",
+                message, message_type, markers
+            );
+            // A message overriding its wire type shares the type of another one.
+            if wire_type.is_none() {
+                registry += &format!("\t{} => MessageType_{},\n", message, message_type);
+            }
+        }
+    }
+    let mut bitcoin_only: Vec<_> = message_types
+        .iter()
+        .filter(|(_, message_type)| message_type.has_option("bitcoin_only"))
+        .map(|(name, message_type)| (message_type.id, name))
+        .collect();
+    bitcoin_only.sort();
+    let bitcoin_only: String = bitcoin_only
+        .iter()
+        .map(|(_, name)| format!("\tMessageType_{},\n", name))
+        .collect();
+    format!(
+        "// This file is generated by build.rs from the protos, do not edit.\n\n{}\nany_trezor_message! {{\n{}}}\n\nbitcoin_only_message_types! {{\n{}}}\n",
+        impls, registry, bitcoin_only
+    )
+}
+
+/// Collect the `MessageType` entries with their ids, and the top-level messages with
+/// the wire type they override.
+fn parse_proto(
+    source: &str,
+    message_types: &mut BTreeMap<String, MessageType>,
+    messages: &mut Vec<(String, Option<u32>)>,
+) {
+    let mut in_comment = false;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        // Some entries are commented out in `/* */` blocks.
+        if trimmed.starts_with("/*") {
+            in_comment = true;
+        }
+        if in_comment {
+            in_comment = !trimmed.ends_with("*/");
+            continue;
+        }
+        if trimmed.starts_with("//") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("message ") {
+            let name = rest
+                .split(|c: char| c.is_whitespace() || c == '{')
+                .next()
+                .unwrap_or_default();
+            messages.push((name.to_owned(), None));
+        } else if let Some(rest) = trimmed.strip_prefix("MessageType_") {
+            let mut parts = rest.splitn(2, '=');
+            let name = parts.next().unwrap_or_default().trim();
+            let value = parts.next().unwrap_or_default();
+            if let Some(id) = parse_number(value) {
+                let options = parse_options(value);
+                message_types.insert(name.to_owned(), MessageType { id, options });
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("option (wire_type) =") {
+            if let (Some(message), Some(id)) = (messages.last_mut(), parse_number(rest)) {
+                message.1 = Some(id);
+            }
+        }
+    }
+}
+
+/// Parse the options set to true, f.e. of `0 [(bitcoin_only) = true, (wire_in) = true];`.
+fn parse_options(text: &str) -> Vec<String> {
+    let options = match (text.find('['), text.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &text[start + 1..end],
+        _ => return Vec::new(),
+    };
+    options
+        .split(',')
+        .filter_map(|option| {
+            let mut parts = option.splitn(2, '=');
+            let name = parts
+                .next()?
+                .trim()
+                .trim_start_matches('(')
+                .trim_end_matches(')');
+            match parts.next()?.trim() {
+                "true" => Some(name.to_owned()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse the number at the start of the text, f.e. of `22;` or `0 [(wire_in) = true];`.
+fn parse_number(text: &str) -> Option<u32> {
+    let text = text.trim_start();
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    text[..end].parse().ok()
 }
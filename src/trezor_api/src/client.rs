@@ -1,8 +1,22 @@
+use std::cmp;
 use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use super::{protos, Error, Result, TrezorModel};
-use crate::messages::TrezorMessage;
-use crate::transport::{ProtoMessage, Transport};
+use futures::channel::mpsc;
+use futures::future::{self, Either};
+use futures::StreamExt;
+use log::{debug, trace, warn};
+
+use super::{
+    entropy, protos, AvailableDevice, Capability, DeviceFailure, Error, FirmwareVersion,
+    Requirement, Result, TrezorModel,
+};
+use crate::logging::Payload;
+use crate::messages::{self, DeviceToHost, HostToDevice, TrezorMessage};
+use crate::reboot::DeviceIdentity;
+use crate::support;
+use crate::time;
+use crate::transport::{self, ProtoMessage, Transport, TransportConfig};
 use protos::Address as BitcoinAddress;
 use protos::KeyDerivationPath;
 use protos::MessageType::*;
@@ -13,6 +27,12 @@ pub use protos::Features;
 pub use protos::PinMatrixRequest_PinMatrixRequestType as PinMatrixRequestType;
 pub use protos::{TezosAddress, TezosPublicKey, TezosSignTx, TezosSignedTx};
 
+/// The id of the next call, to tell the calls apart in the logs.
+static NEXT_CALL_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// The maximum number of bytes the device returns for a single `GetEntropy` request.
+const ENTROPY_CHUNK_SIZE: usize = 1024;
+
 /// The different options for the number of words in a seed phrase.
 pub enum WordCount {
     W12 = 12,
@@ -35,19 +55,19 @@ pub enum InteractionType {
 pub type ResultHandler<'a, T, R> = dyn Fn(&'a mut Trezor, R) -> Result<T>;
 
 /// A button request message sent by the device.
-pub struct ButtonRequest<'a, T, R: TrezorMessage> {
+pub struct ButtonRequest<'a, T, R: DeviceToHost> {
     message: protos::ButtonRequest,
     client: &'a mut Trezor,
     result_handler: Box<ResultHandler<'a, T, R>>,
 }
 
-impl<'a, T, R: TrezorMessage> fmt::Debug for ButtonRequest<'a, T, R> {
+impl<'a, T, R: DeviceToHost> fmt::Debug for ButtonRequest<'a, T, R> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(&self.message, f)
     }
 }
 
-impl<'a, T, R: TrezorMessage> ButtonRequest<'a, T, R> {
+impl<'a, T, R: DeviceToHost> ButtonRequest<'a, T, R> {
     /// The type of button request.
     pub fn request_type(&self) -> ButtonRequestType {
         self.message.get_code()
@@ -58,22 +78,30 @@ impl<'a, T, R: TrezorMessage> ButtonRequest<'a, T, R> {
         let req = protos::ButtonAck::new();
         self.client.call(req, self.result_handler).await
     }
+
+    /// Abort the interaction instead of acking it.
+    ///
+    /// The device is expected to respond with a `Failure`.
+    pub async fn cancel(self) -> Result<TrezorResponse<'a, T, R>> {
+        let req = protos::Cancel::new();
+        self.client.call(req, self.result_handler).await
+    }
 }
 
 /// A PIN matrix request message sent by the device.
-pub struct PinMatrixRequest<'a, T, R: TrezorMessage> {
+pub struct PinMatrixRequest<'a, T, R: DeviceToHost> {
     message: protos::PinMatrixRequest,
     client: &'a mut Trezor,
     result_handler: Box<ResultHandler<'a, T, R>>,
 }
 
-impl<'a, T, R: TrezorMessage> fmt::Debug for PinMatrixRequest<'a, T, R> {
+impl<'a, T, R: DeviceToHost> fmt::Debug for PinMatrixRequest<'a, T, R> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(&self.message, f)
     }
 }
 
-impl<'a, T, R: TrezorMessage> PinMatrixRequest<'a, T, R> {
+impl<'a, T, R: DeviceToHost> PinMatrixRequest<'a, T, R> {
     /// The type of PIN matrix request.
     pub fn request_type(&self) -> PinMatrixRequestType {
         self.message.get_field_type()
@@ -85,6 +113,14 @@ impl<'a, T, R: TrezorMessage> PinMatrixRequest<'a, T, R> {
         req.set_pin(pin);
         self.client.call(req, self.result_handler).await
     }
+
+    /// Abort the interaction instead of entering a PIN.
+    ///
+    /// The device is expected to respond with a `Failure`.
+    pub async fn cancel(self) -> Result<TrezorResponse<'a, T, R>> {
+        let req = protos::Cancel::new();
+        self.client.call(req, self.result_handler).await
+    }
 }
 
 /// A response from a Trezor device.
@@ -92,14 +128,14 @@ impl<'a, T, R: TrezorMessage> PinMatrixRequest<'a, T, R> {
 /// On every message exchange, instead of the expected/desired response,
 /// the Trezor can ask for some user interaction, or can send a failure.
 #[derive(Debug)]
-pub enum TrezorResponse<'a, T, R: TrezorMessage> {
+pub enum TrezorResponse<'a, T, R: DeviceToHost> {
     Ok(T),
     Failure(protos::Failure),
     ButtonRequest(ButtonRequest<'a, T, R>),
     PinMatrixRequest(PinMatrixRequest<'a, T, R>),
 }
 
-impl<'a, T, R: TrezorMessage> fmt::Display for TrezorResponse<'a, T, R> {
+impl<'a, T, R: DeviceToHost> fmt::Display for TrezorResponse<'a, T, R> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TrezorResponse::Ok(ref _m) => write!(f, "Ok"), //TODO(stevenroose) should we make T: Debug?
@@ -110,12 +146,12 @@ impl<'a, T, R: TrezorMessage> fmt::Display for TrezorResponse<'a, T, R> {
     }
 }
 
-impl<'a, T, R: TrezorMessage> TrezorResponse<'a, T, R> {
+impl<'a, T, R: DeviceToHost> TrezorResponse<'a, T, R> {
     /// Get the actual `Ok` response value or an error if not `Ok`.
     pub fn ok(self) -> Result<T> {
         match self {
             TrezorResponse::Ok(m) => Ok(m),
-            TrezorResponse::Failure(m) => Err(Error::FailureResponse(m)),
+            TrezorResponse::Failure(m) => Err(Error::FailureResponse(m.into())),
             TrezorResponse::ButtonRequest(_) => {
                 Err(Error::UnexpectedInteractionRequest(InteractionType::Button))
             }
@@ -130,7 +166,7 @@ impl<'a, T, R: TrezorMessage> TrezorResponse<'a, T, R> {
         match self {
             TrezorResponse::ButtonRequest(r) => Ok(r),
             TrezorResponse::Ok(_) => Err(Error::UnexpectedMessageType(R::message_type())),
-            TrezorResponse::Failure(m) => Err(Error::FailureResponse(m)),
+            TrezorResponse::Failure(m) => Err(Error::FailureResponse(m.into())),
             TrezorResponse::PinMatrixRequest(_) => Err(Error::UnexpectedInteractionRequest(
                 InteractionType::PinMatrix,
             )),
@@ -142,7 +178,7 @@ impl<'a, T, R: TrezorMessage> TrezorResponse<'a, T, R> {
         match self {
             TrezorResponse::PinMatrixRequest(r) => Ok(r),
             TrezorResponse::Ok(_) => Err(Error::UnexpectedMessageType(R::message_type())),
-            TrezorResponse::Failure(m) => Err(Error::FailureResponse(m)),
+            TrezorResponse::Failure(m) => Err(Error::FailureResponse(m.into())),
             TrezorResponse::ButtonRequest(_) => {
                 Err(Error::UnexpectedInteractionRequest(InteractionType::Button))
             }
@@ -161,7 +197,7 @@ impl<'a, T, R: TrezorMessage> TrezorResponse<'a, T, R> {
                     return Ok(val);
                 }
                 Self::Failure(err) => {
-                    return Err(Error::FailureResponse(err));
+                    return Err(Error::FailureResponse(err.into()));
                 }
                 Self::ButtonRequest(req) => req.ack().await?,
                 Self::PinMatrixRequest(_) => {
@@ -195,20 +231,72 @@ impl<'a> EntropyRequest<'a> {
     }
 }
 
+/// The outcome of resuming a session with [Trezor::init_device_with_session].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SessionStatus {
+    /// The device resumed the requested session.
+    Resumed,
+    /// The device didn't know the requested session and started a new one.
+    Started,
+}
+
+/// A handle to cancel the interaction a [Trezor] is waiting on.
+///
+/// It can be cloned and sent to another task, f.e. to abort a [ButtonRequest::ack]
+/// that waits for the user to confirm an action on the device.
+#[derive(Clone, Debug)]
+pub struct CancelHandle {
+    cancel_tx: mpsc::UnboundedSender<()>,
+}
+
+impl CancelHandle {
+    /// Ask the device to abort the pending interaction.
+    ///
+    /// The waiting call will receive a `Failure` from the device.
+    /// Does nothing if the client is not waiting for the device at the moment.
+    pub fn cancel(&self) {
+        // The client is gone, so there is nothing to cancel.
+        self.cancel_tx.unbounded_send(()).ok();
+    }
+}
+
 /// A Trezor client.
 pub struct Trezor {
     model: TrezorModel,
     // Cached features for later inspection.
     features: Option<protos::Features>,
     transport: Box<dyn Transport>,
+    config: TransportConfig,
+    // The session to resume on `Initialize`.
+    session_id: Option<Vec<u8>>,
+    cancel_tx: mpsc::UnboundedSender<()>,
+    cancel_rx: mpsc::UnboundedReceiver<()>,
+    // The device connected to, to find it again after a reboot.
+    pub(crate) identity: Option<DeviceIdentity>,
+}
+
+fn log_received(call_id: usize, resp: &ProtoMessage) {
+    debug!(
+        "call {}: received {:?} ({} bytes)",
+        call_id,
+        resp.message_type(),
+        resp.payload().len()
+    );
+    trace!("call {}: payload {}", call_id, Payload(resp));
 }
 
 /// Create a new Trezor instance with the given transport.
 pub fn trezor_with_transport(model: TrezorModel, transport: Box<dyn Transport>) -> Trezor {
+    let (cancel_tx, cancel_rx) = mpsc::unbounded();
     Trezor {
         model,
         transport,
+        config: TransportConfig::default(),
         features: None,
+        session_id: None,
+        cancel_tx,
+        cancel_rx,
+        identity: None,
     }
 }
 
@@ -223,21 +311,216 @@ impl Trezor {
         self.features.as_ref()
     }
 
+    /// Whether the device runs the Bitcoin-only firmware, going by the capabilities
+    /// reported in the features.
+    ///
+    /// The calls the Bitcoin-only firmware doesn't handle fail early with
+    /// [Error::BitcoinOnlyFirmware] then.
+    pub fn is_bitcoin_only(&self) -> bool {
+        use protos::Features_Capability::*;
+
+        let capabilities = match self.features {
+            Some(ref features) => features.get_capabilities(),
+            None => return false,
+        };
+        !capabilities.is_empty()
+            && capabilities.iter().all(|capability| {
+                matches!(
+                    capability,
+                    Capability_Bitcoin
+                        | Capability_Crypto
+                        | Capability_Shamir
+                        | Capability_ShamirGroups
+                        | Capability_PassphraseEntry
+                        | Capability_U2F
+                )
+            })
+    }
+
+    /// Get the firmware version reported in the features.
+    pub fn firmware_version(&self) -> Option<FirmwareVersion> {
+        self.features.as_ref().map(FirmwareVersion::from_features)
+    }
+
+    /// Whether the firmware reports the capability in the features.
+    ///
+    /// It's false before [Trezor::init_device] and on the firmwares too old to report
+    /// their capabilities.
+    pub fn supports(&self, capability: Capability) -> bool {
+        match self.features {
+            Some(ref features) => features
+                .get_capabilities()
+                .iter()
+                .any(|&c| Capability::from(c) == capability),
+            None => false,
+        }
+    }
+
+    /// Check that the firmware handles the message type, going by the features.
+    ///
+    /// Without the features, or when the firmware doesn't report its capabilities,
+    /// nothing is checked and the device answers for itself.
+    pub fn check_supported(&self, message_type: protos::MessageType) -> Result<()> {
+        let features = match self.features {
+            Some(ref features) => features,
+            None => return Ok(()),
+        };
+        let actual = FirmwareVersion::from_features(features);
+        if let Some(capability) = support::required_capability(message_type) {
+            if !features.get_capabilities().is_empty() && !self.supports(capability) {
+                return Err(Error::Unsupported {
+                    needed: Requirement::Capability(capability),
+                    actual,
+                });
+            }
+        }
+        if let Some(version) = support::minimum_version(message_type, self.model) {
+            if actual < version {
+                return Err(Error::Unsupported {
+                    needed: Requirement::Version(version),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the id of the current session.
+    ///
+    /// It can be saved to resume the session with [Trezor::init_device_with_session]
+    /// after reconnecting to the device.
+    pub fn session_id(&self) -> Option<&[u8]> {
+        self.session_id.as_deref()
+    }
+
+    /// Get the timeouts of the calls.
+    pub fn transport_config(&self) -> &TransportConfig {
+        &self.config
+    }
+
+    /// Set the timeouts of the calls.
+    ///
+    /// A call waiting longer than the timeout cancels the request on the device and
+    /// fails with [transport::Error::DeviceReadTimeout].
+    pub fn set_transport_config(&mut self, config: TransportConfig) {
+        self.transport.set_config(&config);
+        self.config = config;
+    }
+
+    /// Get a handle to cancel the interaction this client is waiting on.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle {
+            cancel_tx: self.cancel_tx.clone(),
+        }
+    }
+
     /// Sends a message and returns the raw ProtoMessage struct that was
     /// responded by the device.
     ///
     /// This method is only exported for users that want to expand the
     /// features of this library f.e. for supporting additional coins etc.
-    pub async fn call_raw<S: TrezorMessage>(&mut self, message: S) -> Result<ProtoMessage> {
+    pub async fn call_raw<S: HostToDevice>(&mut self, message: S) -> Result<ProtoMessage> {
+        if self.is_bitcoin_only() && !messages::is_bitcoin_only(S::message_type()) {
+            return Err(Error::BitcoinOnlyFirmware(S::message_type()));
+        }
+        self.check_supported(S::message_type())?;
+
+        // Forget the cancel requests that came while we were not waiting for the device.
+        while self.cancel_rx.try_recv().is_ok() {}
+
+        // After a `ButtonAck` the device waits for the user to confirm on its screen.
+        let timeout = match S::message_type() {
+            MessageType_ButtonAck => self.config.user_timeout,
+            _ => self.config.device_timeout,
+        };
+
+        let call_id = NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed);
+        self.write_message(call_id, message).await?;
+        let timed_out = {
+            let read = self.transport.read_message();
+            let deadline = Box::pin(time::sleep(timeout));
+            match future::select(read, future::select(self.cancel_rx.next(), deadline)).await {
+                // The link gave up waiting before the deadline.
+                Either::Left((Err(transport::Error::DeviceReadTimeout), _)) => true,
+                Either::Left((resp, _)) => {
+                    let resp = resp.map_err(Error::TransportReceiveMessage)?;
+                    log_received(call_id, &resp);
+                    return Ok(resp);
+                }
+                Either::Right((Either::Left(_), _)) => false,
+                Either::Right((Either::Right(_), _)) => true,
+            }
+        };
+
+        match timed_out {
+            true => warn!(
+                "call {}: timed out after {:?}, cancelling",
+                call_id, timeout
+            ),
+            false => debug!("call {}: cancelled", call_id),
+        }
+        // The transport keeps the message it was reading, so the read resumes below.
+        self.write_message(call_id, protos::Cancel::new()).await?;
+        let resp = self.read_cancel_failure(call_id).await?;
+        if timed_out {
+            let err = transport::Error::DeviceReadTimeout;
+            return Err(Error::TransportReceiveMessage(err));
+        }
+        Ok(resp)
+    }
+
+    /// Read up to the `Failure` the device answers a `Cancel` with.
+    ///
+    /// The answer to the cancelled request may have been on its way already, it is
+    /// skipped then, and so is the `Failure` the device answers the extra `Cancel` with.
+    async fn read_cancel_failure(&mut self, call_id: usize) -> Result<ProtoMessage> {
+        let mut deadline = Box::pin(time::sleep(self.config.device_timeout));
+        let mut stale = false;
+        loop {
+            let read = self.transport.read_message();
+            let resp = match future::select(read, &mut deadline).await {
+                Either::Left((resp, _)) => resp.map_err(Error::TransportReceiveMessage)?,
+                Either::Right(_) => {
+                    warn!(
+                        "call {}: the device didn't answer the cancellation",
+                        call_id
+                    );
+                    let err = transport::Error::DeviceReadTimeout;
+                    return Err(Error::TransportReceiveMessage(err));
+                }
+            };
+            log_received(call_id, &resp);
+            if resp.message_type() != MessageType_Failure {
+                debug!(
+                    "call {}: skipping the answer to the cancelled request",
+                    call_id
+                );
+                stale = true;
+                continue;
+            }
+            // Without an answer first, only `ActionCancelled` is for the pending request.
+            let failure: protos::Failure = resp.clone().into_message()?;
+            if stale || failure.get_code() == protos::Failure_FailureType::Failure_ActionCancelled {
+                return Ok(resp);
+            }
+            debug!("call {}: skipping a stale failure", call_id);
+            stale = true;
+        }
+    }
+
+    async fn write_message<S: TrezorMessage>(&mut self, call_id: usize, message: S) -> Result<()> {
         let proto_msg = ProtoMessage(S::message_type(), message.write_to_bytes()?);
+        debug!(
+            "call {}: sending {:?} ({} bytes)",
+            call_id,
+            proto_msg.message_type(),
+            proto_msg.payload().len()
+        );
+        trace!("call {}: payload {}", call_id, Payload(&proto_msg));
         self.transport
             .write_message(proto_msg)
             .await
-            .map_err(|e| Error::TransportSendMessage(e))?;
-        self.transport
-            .read_message()
-            .await
-            .map_err(|e| Error::TransportReceiveMessage(e))
+            .map_err(Error::TransportSendMessage)
     }
 
     /// Sends a message and returns a TrezorResponse with either the
@@ -245,27 +528,32 @@ impl Trezor {
     ///
     /// This method is only exported for users that want to expand the
     /// features of this library f.e. for supporting additional coins etc.
-    pub async fn call<'a, T, S: TrezorMessage, R: TrezorMessage>(
+    pub async fn call<'a, T, S: HostToDevice, R: DeviceToHost>(
         &'a mut self,
         message: S,
         result_handler: Box<ResultHandler<'a, T, R>>,
     ) -> Result<TrezorResponse<'a, T, R>> {
-        // trace!("Sending {:?} msg: {:?}", S::message_type(), message);
         let resp = self.call_raw(message).await?;
         if resp.message_type() == R::message_type() {
             let resp_msg = resp.into_message()?;
-            // trace!("Received {:?} msg: {:?}", R::message_type(), resp_msg);
             Ok(TrezorResponse::Ok(result_handler(self, resp_msg)?))
         } else {
             match resp.message_type() {
                 MessageType_Failure => {
-                    let fail_msg = resp.into_message()?;
-                    // debug!("Received failure: {:?}", fail_msg);
+                    let fail_msg: protos::Failure = resp.into_message()?;
+                    debug!(
+                        "the device failed with {:?}: {}",
+                        fail_msg.get_code(),
+                        fail_msg.get_message()
+                    );
                     Ok(TrezorResponse::Failure(fail_msg))
                 }
                 MessageType_ButtonRequest => {
-                    let req_msg = resp.into_message()?;
-                    // trace!("Received ButtonRequest: {:?}", req_msg);
+                    let req_msg: protos::ButtonRequest = resp.into_message()?;
+                    debug!(
+                        "the device asks for a button press: {:?}",
+                        req_msg.get_code()
+                    );
                     Ok(TrezorResponse::ButtonRequest(ButtonRequest {
                         result_handler,
                         message: req_msg,
@@ -273,8 +561,8 @@ impl Trezor {
                     }))
                 }
                 MessageType_PinMatrixRequest => {
-                    let req_msg = resp.into_message()?;
-                    // trace!("Received PinMatrixRequest: {:?}", req_msg);
+                    let req_msg: protos::PinMatrixRequest = resp.into_message()?;
+                    debug!("the device asks for a PIN: {:?}", req_msg.get_field_type());
                     Ok(TrezorResponse::PinMatrixRequest(PinMatrixRequest {
                         result_handler,
                         message: req_msg,
@@ -282,11 +570,11 @@ impl Trezor {
                     }))
                 }
                 mtype => {
-                    // debug!(
-                    // 	"Received unexpected msg type: {:?}; raw msg: {}",
-                    // 	mtype,
-                    // 	hex::encode(resp.into_payload())
-                    // );
+                    warn!(
+                        "received an unexpected {:?} while waiting for {:?}",
+                        mtype,
+                        R::message_type()
+                    );
                     Err(Error::UnexpectedMessageType(mtype))
                 }
             }
@@ -297,13 +585,91 @@ impl Trezor {
     ///
     /// Warning: Must be called before sending requests to Trezor.
     pub async fn init_device(&mut self) -> Result<()> {
+        self.transport
+            .session_begin()
+            .await
+            .map_err(Error::TransportBeginSession)?;
         let features = self.initialize().await?.ok()?;
         self.features = Some(features);
         Ok(())
     }
 
+    /// End the transport session and close the connection to the device.
+    pub async fn close(mut self) -> Result<()> {
+        self.session_end().await
+    }
+
+    /// End the transport session, keeping the connection to the device.
+    pub(crate) async fn session_end(&mut self) -> Result<()> {
+        self.transport
+            .session_end()
+            .await
+            .map_err(Error::TransportEndSession)
+    }
+
+    /// Begin the transport session and cache the `Features` like [Trezor::init_device],
+    /// but read them with `GetFeatures`, so the sessions of other clients of the device
+    /// survive.
+    ///
+    /// Firmware without `GetFeatures` is initialized instead, resuming the cached session
+    /// if there is one.
+    pub(crate) async fn probe_features(&mut self) -> Result<()> {
+        self.transport
+            .session_begin()
+            .await
+            .map_err(Error::TransportBeginSession)?;
+        let features = match self.get_features().await.and_then(|r| r.ok()) {
+            Err(Error::FailureResponse(DeviceFailure::UnexpectedMessage(_))) => {
+                self.initialize().await?.ok()?
+            }
+            res => res?,
+        };
+        self.features = Some(features);
+        Ok(())
+    }
+
+    /// Initialize the device resuming the session with the given id,
+    /// f.e. the one saved by [Trezor::session_id] before the device was replugged.
+    ///
+    /// If the device doesn't know the session anymore, it silently starts a new one
+    /// and [SessionStatus::Started] is returned. The passphrase will be asked again then.
+    pub async fn init_device_with_session(&mut self, session_id: Vec<u8>) -> Result<SessionStatus> {
+        self.session_id = Some(session_id.clone());
+        self.init_device().await?;
+        if self.session_id.as_ref() == Some(&session_id) {
+            Ok(SessionStatus::Resumed)
+        } else {
+            Ok(SessionStatus::Started)
+        }
+    }
+
+    /// Send `Initialize` resuming the cached session if there is one.
+    ///
+    /// The session id returned by the device is cached.
     pub async fn initialize(&mut self) -> Result<TrezorResponse<'_, Features, Features>> {
-        let req = protos::Initialize::new();
+        let mut req = protos::Initialize::new();
+        if let Some(session_id) = self.session_id.clone() {
+            req.set_session_id(session_id);
+        }
+        self.call(
+            req,
+            Box::new(|client, m: Features| {
+                client.session_id = if m.has_session_id() {
+                    Some(m.get_session_id().to_vec())
+                } else {
+                    None
+                };
+                Ok(m)
+            }),
+        )
+        .await
+    }
+
+    /// Read the `Features` without touching the session, unlike [Trezor::initialize].
+    ///
+    /// Firmware without `GetFeatures` fails with [DeviceFailure::UnexpectedMessage].
+    pub async fn get_features(&mut self) -> Result<TrezorResponse<'_, Features, Features>> {
+        let req = protos::GetFeatures::new();
         self.call(req, Box::new(|_, m| Ok(m))).await
     }
 
@@ -313,6 +679,121 @@ impl Trezor {
         self.call(req, Box::new(|_, _| Ok(()))).await
     }
 
+    /// Lock the device, following actions will require the PIN.
+    ///
+    /// Cached passphrases remain valid.
+    pub async fn lock(&mut self) -> Result<TrezorResponse<'_, (), protos::Success>> {
+        let req = protos::LockDevice::new();
+        self.call(req, Box::new(|_, _| Ok(()))).await
+    }
+
+    /// End the current session, the session cache of the device is discarded.
+    ///
+    /// Warning: [Trezor::init_device] must be called again before sending other requests.
+    pub async fn end_session(&mut self) -> Result<TrezorResponse<'_, (), protos::Success>> {
+        let req = protos::EndSession::new();
+        self.call(
+            req,
+            Box::new(|client, _| {
+                client.session_id = None;
+                Ok(())
+            }),
+        )
+        .await
+    }
+
+    /// Get `size` bytes of random data generated by the hardware RNG of the device.
+    ///
+    /// Requests larger than the device accepts are split into chunks.
+    /// The device may ask the user to confirm every chunk, this method waits for it.
+    /// Fails with [Error::EmptyEntropy] if the device returns an empty chunk.
+    pub async fn get_entropy(&mut self, size: usize) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(size);
+        while data.len() < size {
+            let chunk_size = cmp::min(size - data.len(), ENTROPY_CHUNK_SIZE);
+            let mut req = protos::GetEntropy::new();
+            req.set_size(chunk_size as u32);
+
+            let chunk = self
+                .call(
+                    req,
+                    Box::new(|_, m: protos::Entropy| Ok(m.get_entropy().to_vec())),
+                )
+                .await?
+                .ack_all()
+                .await?;
+            if chunk.is_empty() {
+                return Err(Error::EmptyEntropy);
+            }
+            data.extend(chunk.into_iter().take(chunk_size));
+        }
+        Ok(data)
+    }
+
+    /// Same as [Trezor::get_entropy], but also runs the [entropy::health_check]
+    /// on the returned data.
+    ///
+    /// `size` must be at least [entropy::MIN_HEALTH_CHECK_BITS] / 8 bytes.
+    pub async fn get_checked_entropy(&mut self, size: usize) -> Result<Vec<u8>> {
+        let data = self.get_entropy(size).await?;
+        entropy::health_check(&data).map_err(Error::EntropyHealthCheck)?;
+        Ok(data)
+    }
+
+    /// Get the next value of the FIDO U2F counter.
+    ///
+    /// The device increments the counter and may ask the user to confirm.
+    pub async fn get_next_u2f_counter(
+        &mut self,
+    ) -> Result<TrezorResponse<'_, u32, protos::NextU2FCounter>> {
+        let req = protos::GetNextU2FCounter::new();
+        self.call(
+            req,
+            Box::new(|_, m: protos::NextU2FCounter| Ok(m.get_u2f_counter())),
+        )
+        .await
+    }
+
+    /// Set the FIDO U2F counter, f.e. to carry it over from another device.
+    ///
+    /// The device may ask the user to confirm.
+    pub async fn set_u2f_counter(
+        &mut self,
+        counter: u32,
+    ) -> Result<TrezorResponse<'_, (), protos::Success>> {
+        let mut req = protos::SetU2FCounter::new();
+        req.set_u2f_counter(counter);
+        self.call(req, Box::new(|_, _| Ok(()))).await
+    }
+
+    /// Reboot the device into the bootloader.
+    ///
+    /// The device may ask the user to confirm the reboot, this method waits for it.
+    /// Once the device has confirmed, the connection is closed and the device is
+    /// waited for to re-enumerate in bootloader mode, see [crate::wait_for_bootloader].
+    /// The returned [AvailableDevice] must be connected to again.
+    ///
+    /// Only the devices connected through [AvailableDevice::connect] over USB can be
+    /// found again, the others fail with [Error::UnsupportedReboot] before the reboot.
+    pub async fn reboot_to_bootloader(mut self) -> Result<AvailableDevice> {
+        let identity = self.identity.clone().ok_or(Error::UnsupportedReboot)?;
+        identity.check_reboot()?;
+        // The devices there before the reboot tell the rebooted one apart.
+        let before: Vec<String> = crate::find_devices()
+            .await?
+            .iter()
+            .map(AvailableDevice::id)
+            .collect();
+        let req = protos::RebootToBootloader::new();
+        self.call(req, Box::new(|_, _: protos::Success| Ok(())))
+            .await?
+            .ack_all()
+            .await?;
+        // Release the device before it re-enumerates.
+        drop(self);
+        crate::reboot::wait_for_identity(&identity, &before).await
+    }
+
     /// Get address(public key hash) from Trezor.
     ///
     /// Derives keys from passed `path` (key derivation path), hashes
@@ -372,3 +853,396 @@ impl Trezor {
         self.call(tx, Box::new(|_, m| Ok(m))).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::{MockTransport, Script};
+    use futures::executor::block_on;
+
+    fn trezor_with_script(script: &Script) -> Trezor {
+        trezor_with_transport(TrezorModel::T, Box::new(MockTransport::new(script.clone())))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_call_timeout() {
+        use crate::transport::udp::{AvailableUdpTransport, UdpTransport};
+        use crate::transport::AvailableDeviceTransport;
+        use crate::DeviceMode;
+        use std::net::UdpSocket;
+        use std::time::Duration;
+
+        // A device that never answers.
+        let device = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let transport = UdpTransport::connect(&AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: "127.0.0.1".to_owned(),
+                port: device.local_addr().unwrap().port() as usize,
+            }),
+        })
+        .unwrap();
+
+        let mut trezor = trezor_with_transport(TrezorModel::T, transport);
+        trezor.set_transport_config(TransportConfig {
+            device_timeout: Duration::from_millis(50),
+            user_timeout: Duration::from_millis(50),
+            ..TransportConfig::default()
+        });
+        let res = block_on(trezor.call_raw(ping("hello")));
+        assert!(matches!(
+            res,
+            Err(Error::TransportReceiveMessage(
+                transport::Error::DeviceReadTimeout
+            ))
+        ));
+
+        // The request was cancelled after the timeout.
+        let mut buf = [0; 64];
+        let mut magics = Vec::new();
+        device.set_nonblocking(true).unwrap();
+        while let Ok(n) = device.recv(&mut buf) {
+            if n == 64 && buf[..3] == [0x3f, 0x23, 0x23] {
+                magics.push(u16::from_be_bytes([buf[3], buf[4]]));
+            }
+        }
+        assert_eq!(
+            magics,
+            vec![MessageType_Ping as u16, MessageType_Cancel as u16]
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_cancel_after_answer() {
+        use crate::transport::udp::{AvailableUdpTransport, UdpTransport};
+        use crate::transport::{v1_chunks, AvailableDeviceTransport};
+        use crate::DeviceMode;
+        use protobuf::Message;
+        use std::net::UdpSocket;
+        use std::thread;
+
+        fn success(message: &str) -> ProtoMessage {
+            let mut success = protos::Success::new();
+            success.set_message(message.to_owned());
+            ProtoMessage(MessageType_Success, success.write_to_bytes().unwrap())
+        }
+
+        let device = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let transport = UdpTransport::connect(&AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: "127.0.0.1".to_owned(),
+                port: device.local_addr().unwrap().port() as usize,
+            }),
+        })
+        .unwrap();
+        let mut trezor = trezor_with_transport(TrezorModel::T, transport);
+        let cancel = trezor.cancel_handle();
+
+        // The device answers the first ping, in two chunks, just as it is cancelled.
+        let device = thread::spawn(move || {
+            let mut buf = [0; 64];
+            let (_, host) = device.recv_from(&mut buf).unwrap();
+            let answer = v1_chunks(success(&"a".repeat(100)));
+            device.send_to(&answer[0], host).unwrap();
+            cancel.cancel();
+            device.recv_from(&mut buf).unwrap();
+            assert_eq!(buf[..5], [0x3f, 0x23, 0x23, 0, MessageType_Cancel as u8]);
+            device.send_to(&answer[1], host).unwrap();
+            let mut failure = protos::Failure::new();
+            failure.set_code(protos::Failure_FailureType::Failure_ActionCancelled);
+            let failure = ProtoMessage(MessageType_Failure, failure.write_to_bytes().unwrap());
+            device.send_to(&v1_chunks(failure)[0], host).unwrap();
+
+            device.recv_from(&mut buf).unwrap();
+            device.send_to(&v1_chunks(success("two"))[0], host).unwrap();
+        });
+
+        let res = block_on(trezor.call_raw(ping("one"))).unwrap();
+        assert_eq!(res.message_type(), MessageType_Failure);
+        // The stream is in step again.
+        let res = block_on(trezor.call_raw(ping("two"))).unwrap();
+        assert_eq!(res, success("two"));
+        device.join().unwrap();
+    }
+
+    #[test]
+    fn test_trezor_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Trezor>();
+    }
+
+    fn ping(message: &str) -> protos::Ping {
+        let mut req = protos::Ping::new();
+        req.set_message(message.to_owned());
+        req
+    }
+
+    fn button_request() -> protos::ButtonRequest {
+        let mut req = protos::ButtonRequest::new();
+        req.set_code(ButtonRequestType::ButtonRequest_ProtectCall);
+        req
+    }
+
+    #[test]
+    fn test_call_ok() {
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond(protos::Success::new());
+        let mut trezor = trezor_with_script(&script);
+
+        block_on(async { trezor.ping("test").await?.ok() }).unwrap();
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_call_button_request() {
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond(button_request())
+            .expect(protos::ButtonAck::new())
+            .respond(protos::Success::new());
+        let mut trezor = trezor_with_script(&script);
+
+        block_on(async {
+            let req = trezor.ping("test").await?.button_request()?;
+            assert_eq!(
+                req.request_type(),
+                ButtonRequestType::ButtonRequest_ProtectCall
+            );
+            req.ack().await?.ok()
+        })
+        .unwrap();
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_call_pin_matrix_request() {
+        let mut pin_ack = protos::PinMatrixAck::new();
+        pin_ack.set_pin("1234".to_owned());
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond(protos::PinMatrixRequest::new())
+            .expect(pin_ack)
+            .respond(protos::Success::new());
+        let mut trezor = trezor_with_script(&script);
+
+        block_on(async {
+            let resp = trezor.ping("test").await?;
+            // A PIN can't be acked automatically.
+            assert!(matches!(resp, TrezorResponse::PinMatrixRequest(_)));
+            resp.pin_matrix_request()?
+                .ack_pin("1234".to_owned())
+                .await?
+                .ok()
+        })
+        .unwrap();
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_call_failure() {
+        let mut failure = protos::Failure::new();
+        failure.set_code(protos::Failure_FailureType::Failure_ActionCancelled);
+        failure.set_message("Action cancelled by user".to_owned());
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond(button_request())
+            .expect(protos::ButtonAck::new())
+            .respond(failure);
+        let mut trezor = trezor_with_script(&script);
+
+        let err = block_on(async { trezor.ping("test").await?.ack_all().await }).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the device failed: action cancelled: Action cancelled by user"
+        );
+        match err {
+            Error::FailureResponse(crate::DeviceFailure::ActionCancelled(message)) => {
+                assert_eq!(message, "Action cancelled by user")
+            }
+            _ => panic!("expected a failure"),
+        }
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_get_entropy_empty() {
+        let mut req = protos::GetEntropy::new();
+        req.set_size(32);
+        let mut entropy = protos::Entropy::new();
+        entropy.set_entropy(Vec::new());
+        let script = Script::new().expect(req).respond(entropy);
+        let mut trezor = trezor_with_script(&script);
+
+        let res = block_on(trezor.get_entropy(32));
+        assert!(matches!(res, Err(Error::EmptyEntropy)));
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_get_entropy_chunks() {
+        let mut script = Script::new();
+        let mut expected: Vec<u8> = Vec::new();
+        for (i, &size) in [1024, 1024, 452].iter().enumerate() {
+            let mut req = protos::GetEntropy::new();
+            req.set_size(size as u32);
+            let chunk = vec![i as u8; size];
+            expected.extend(&chunk);
+            let mut entropy = protos::Entropy::new();
+            entropy.set_entropy(chunk);
+            script = script.expect(req).respond(entropy);
+        }
+        let mut trezor = trezor_with_script(&script);
+
+        assert_eq!(block_on(trezor.get_entropy(2500)).unwrap(), expected);
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_get_next_u2f_counter() {
+        let mut counter = protos::NextU2FCounter::new();
+        counter.set_u2f_counter(42);
+        let script = Script::new()
+            .expect(protos::GetNextU2FCounter::new())
+            .respond(counter);
+        let mut trezor = trezor_with_script(&script);
+
+        let counter = block_on(async { trezor.get_next_u2f_counter().await?.ok() }).unwrap();
+        assert_eq!(counter, 42);
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_set_u2f_counter() {
+        let mut req = protos::SetU2FCounter::new();
+        req.set_u2f_counter(7);
+        let script = Script::new()
+            .expect(req.clone())
+            .respond(button_request())
+            .expect(protos::ButtonAck::new())
+            .respond(protos::Success::new());
+        let mut trezor = trezor_with_script(&script);
+
+        block_on(async { trezor.set_u2f_counter(7).await?.ack_all().await }).unwrap();
+        assert!(script.is_finished());
+
+        // The user rejects the new counter.
+        let mut failure = protos::Failure::new();
+        failure.set_code(protos::Failure_FailureType::Failure_ActionCancelled);
+        let script = Script::new()
+            .expect(req)
+            .respond(button_request())
+            .expect(protos::ButtonAck::new())
+            .respond(failure);
+        let mut trezor = trezor_with_script(&script);
+
+        let res = block_on(async { trezor.set_u2f_counter(7).await?.ack_all().await });
+        assert!(matches!(
+            res,
+            Err(Error::FailureResponse(DeviceFailure::ActionCancelled(_)))
+        ));
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_get_next_u2f_counter_failure() {
+        let mut failure = protos::Failure::new();
+        failure.set_code(protos::Failure_FailureType::Failure_UnexpectedMessage);
+        let script = Script::new()
+            .expect(protos::GetNextU2FCounter::new())
+            .respond(failure);
+        let mut trezor = trezor_with_script(&script);
+
+        let res = block_on(async { trezor.get_next_u2f_counter().await?.ok() });
+        assert!(matches!(
+            res,
+            Err(Error::FailureResponse(DeviceFailure::UnexpectedMessage(_)))
+        ));
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_call_unexpected_message() {
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond_raw(ProtoMessage(MessageType_Features, vec![]));
+        let mut trezor = trezor_with_script(&script);
+
+        let res = block_on(trezor.ping("test"));
+        assert!(matches!(
+            res,
+            Err(Error::UnexpectedMessageType(MessageType_Features))
+        ));
+    }
+
+    #[test]
+    fn test_bitcoin_only_firmware() {
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond(protos::Success::new());
+        let mut trezor = trezor_with_script(&script);
+        let mut features = Features::new();
+        features.set_capabilities(vec![
+            protos::Features_Capability::Capability_Bitcoin,
+            protos::Features_Capability::Capability_Crypto,
+        ]);
+        trezor.features = Some(features);
+        assert!(trezor.is_bitcoin_only());
+
+        let res = block_on(trezor.call_raw(protos::TezosGetAddress::new()));
+        assert!(matches!(
+            res,
+            Err(Error::BitcoinOnlyFirmware(MessageType_TezosGetAddress))
+        ));
+        block_on(async { trezor.ping("test").await?.ok() }).unwrap();
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let script = Script::new()
+            .expect(ping("test"))
+            .respond(protos::Success::new());
+        let mut trezor = trezor_with_script(&script);
+        let mut features = Features::new();
+        features.set_major_version(2);
+        features.set_minor_version(2);
+        features.set_patch_version(0);
+        features.set_capabilities(vec![
+            protos::Features_Capability::Capability_Bitcoin,
+            protos::Features_Capability::Capability_Ethereum,
+        ]);
+        trezor.features = Some(features);
+        assert!(trezor.supports(Capability::Ethereum));
+        assert!(!trezor.supports(Capability::Tezos));
+
+        let res = block_on(trezor.call_raw(protos::TezosGetAddress::new()));
+        assert!(matches!(
+            res,
+            Err(Error::Unsupported {
+                needed: Requirement::Capability(Capability::Tezos),
+                ..
+            })
+        ));
+        let res = block_on(trezor.call_raw(protos::ChangeWipeCode::new()));
+        match res {
+            Err(Error::Unsupported {
+                needed: Requirement::Version(needed),
+                actual,
+            }) => {
+                assert_eq!(needed, FirmwareVersion::new(2, 3, 0));
+                assert_eq!(actual, FirmwareVersion::new(2, 2, 0));
+            }
+            _ => panic!("unexpected result"),
+        }
+        block_on(async { trezor.ping("test").await?.ok() }).unwrap();
+        assert!(script.is_finished());
+    }
+}
@@ -0,0 +1,325 @@
+//! # Debug link
+//!
+//! Debug builds of the firmware, f.e. the emulator, expose a second interface that
+//! drives the device like a user would and reveals its secrets. [DebugLink] runs next
+//! to a [Trezor] connected to the same device, so the tests can confirm the prompts
+//! without a human.
+
+use protobuf::ProtobufError;
+
+use super::{protos, AvailableDevice, Error, Result, TrezorModel};
+use crate::messages::{DebugOnly, TrezorMessage};
+use crate::transport::{self, Transport};
+use protos::MessageType::*;
+
+// Some types with raw protos that we use in the public interface so they have to be exported.
+pub use protos::DebugLinkDecision_DebugSwipeDirection as SwipeDirection;
+pub use protos::{DebugLinkDecision, DebugLinkState};
+
+/// A client of the debug link of a device.
+pub struct DebugLink {
+    model: TrezorModel,
+    transport: Box<dyn Transport>,
+}
+
+/// Create a new DebugLink instance with the given transport, which must be
+/// connected to the debug interface.
+pub fn debug_link_with_transport(model: TrezorModel, transport: Box<dyn Transport>) -> DebugLink {
+    DebugLink { model, transport }
+}
+
+impl AvailableDevice {
+    /// Connect to the debug link of the device.
+    ///
+    /// The device found by the enumeration is usually connected with
+    /// [AvailableDevice::connect], so enumerate again to get one for the debug link.
+    pub fn connect_debug_link(mut self) -> Result<DebugLink> {
+        self.debug = true;
+        let t = transport::connect(&self).map_err(Error::TransportConnect)?;
+        Ok(debug_link_with_transport(self.model, t))
+    }
+}
+
+impl DebugLink {
+    pub fn model(&self) -> TrezorModel {
+        self.model
+    }
+
+    /// Make a decision on the current screen.
+    ///
+    /// The device doesn't answer decisions, unless asked to wait for the next layout,
+    /// see [DebugLink::decision_layout].
+    pub async fn decision(&mut self, decision: DebugLinkDecision) -> Result<()> {
+        self.write(decision).await
+    }
+
+    /// Make a decision and return the text of the layout shown afterwards.
+    ///
+    /// Trezor Model T only.
+    pub async fn decision_layout(
+        &mut self,
+        mut decision: DebugLinkDecision,
+    ) -> Result<Vec<String>> {
+        decision.set_wait(true);
+        let layout: protos::DebugLinkLayout = self.call(decision).await?;
+        Ok(layout.get_lines().to_vec())
+    }
+
+    /// Press the confirm button.
+    pub async fn press_yes(&mut self) -> Result<()> {
+        let mut decision = DebugLinkDecision::new();
+        decision.set_yes_no(true);
+        self.decision(decision).await
+    }
+
+    /// Press the cancel button.
+    pub async fn press_no(&mut self) -> Result<()> {
+        let mut decision = DebugLinkDecision::new();
+        decision.set_yes_no(false);
+        self.decision(decision).await
+    }
+
+    /// Swipe the screen in the given direction.
+    pub async fn swipe(&mut self, direction: SwipeDirection) -> Result<()> {
+        let mut decision = DebugLinkDecision::new();
+        decision.set_swipe(direction);
+        self.decision(decision).await
+    }
+
+    /// Type the text on the on-screen keyboard.
+    pub async fn input(&mut self, text: &str) -> Result<()> {
+        let mut decision = DebugLinkDecision::new();
+        decision.set_input(text.to_owned());
+        self.decision(decision).await
+    }
+
+    /// Touch the screen at the given coordinates.
+    pub async fn click(&mut self, x: u32, y: u32) -> Result<()> {
+        let mut decision = DebugLinkDecision::new();
+        decision.set_x(x);
+        decision.set_y(y);
+        self.decision(decision).await
+    }
+
+    /// Get the state of the device.
+    pub async fn state(&mut self) -> Result<DebugLinkState> {
+        self.call(protos::DebugLinkGetState::new()).await
+    }
+
+    /// Get the text of the current layout.
+    pub async fn layout(&mut self) -> Result<Vec<String>> {
+        Ok(self.state().await?.get_layout_lines().to_vec())
+    }
+
+    /// Wait for the layout to change and get its text.
+    ///
+    /// Only works while the layout is watched, see [DebugLink::watch_layout].
+    pub async fn wait_layout(&mut self) -> Result<Vec<String>> {
+        let mut req = protos::DebugLinkGetState::new();
+        req.set_wait_layout(true);
+        let state: DebugLinkState = self.call(req).await?;
+        Ok(state.get_layout_lines().to_vec())
+    }
+
+    /// Get the mnemonic the device was set up with.
+    ///
+    /// Only devices with a BIP-39 backup keep the mnemonic, the secret of a SLIP-39
+    /// backup is not text, see [DebugLinkState::get_mnemonic_secret].
+    pub async fn mnemonic(&mut self) -> Result<String> {
+        let secret = self.state().await?.take_mnemonic_secret();
+        String::from_utf8(secret).map_err(|e| Error::Protobuf(ProtobufError::Utf8(e.utf8_error())))
+    }
+
+    /// Get the current PIN matrix, the digits as laid out on the screen of Trezor Model One.
+    pub async fn pin_matrix(&mut self) -> Result<String> {
+        Ok(self.state().await?.take_matrix())
+    }
+
+    /// Encode a PIN with the current PIN matrix, to send it in `PinMatrixAck`.
+    ///
+    /// Trezor Model T takes the PIN as it is. Fails with [Error::InvalidPin] if the
+    /// PIN has a character which is not on the matrix, f.e. `0`.
+    pub async fn encode_pin(&mut self, pin: &str) -> Result<String> {
+        let matrix = self.pin_matrix().await?;
+        if matrix.is_empty() {
+            return Ok(pin.to_owned());
+        }
+        pin.chars()
+            .map(|digit| match matrix.find(digit) {
+                Some(position) => Ok(char::from(b'1' + position as u8)),
+                None => Err(Error::InvalidPin(digit)),
+            })
+            .collect()
+    }
+
+    /// Re-seed the random number generator of the device, so it makes the same choices
+    /// on every run.
+    pub async fn reseed(&mut self, value: u32) -> Result<()> {
+        let mut req = protos::DebugLinkReseedRandom::new();
+        req.set_value(value);
+        self.call::<_, protos::Success>(req).await?;
+        Ok(())
+    }
+
+    /// Start recording the screens into the given directory on the host of the emulator.
+    pub async fn start_recording(&mut self, directory: &str) -> Result<()> {
+        let mut req = protos::DebugLinkRecordScreen::new();
+        req.set_target_directory(directory.to_owned());
+        self.call::<_, protos::Success>(req).await?;
+        Ok(())
+    }
+
+    /// Stop recording the screens.
+    pub async fn stop_recording(&mut self) -> Result<()> {
+        let req = protos::DebugLinkRecordScreen::new();
+        self.call::<_, protos::Success>(req).await?;
+        Ok(())
+    }
+
+    /// Start or stop tracking the layout changes, see [DebugLink::wait_layout].
+    pub async fn watch_layout(&mut self, watch: bool) -> Result<()> {
+        let mut req = protos::DebugLinkWatchLayout::new();
+        req.set_watch(watch);
+        self.call::<_, protos::Success>(req).await?;
+        Ok(())
+    }
+
+    async fn call<S: DebugOnly, R: TrezorMessage>(&mut self, message: S) -> Result<R> {
+        self.write(message).await?;
+        loop {
+            let resp = self
+                .transport
+                .read_message()
+                .await
+                .map_err(Error::TransportReceiveMessage)?;
+            match resp.message_type() {
+                mtype if mtype == R::message_type() => return Ok(resp.into_message()?),
+                // The firmware sends its logs in between.
+                MessageType_DebugLinkLog => continue,
+                MessageType_Failure => {
+                    return Err(Error::FailureResponse(
+                        resp.into_message::<protos::Failure>()?.into(),
+                    ))
+                }
+                mtype => return Err(Error::UnexpectedMessageType(mtype)),
+            }
+        }
+    }
+
+    async fn write<S: DebugOnly>(&mut self, message: S) -> Result<()> {
+        let proto_msg = transport::ProtoMessage(S::message_type(), message.write_to_bytes()?);
+        self.transport
+            .write_message(proto_msg)
+            .await
+            .map_err(Error::TransportSendMessage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::udp::{AvailableUdpTransport, UdpTransport};
+    use crate::transport::{v1_chunks, AvailableDeviceTransport, ProtoMessage, TransportConfig};
+    use crate::DeviceMode;
+    use byteorder::{BigEndian, ByteOrder};
+    use futures::executor::block_on;
+    use protobuf::{Message, ProtobufEnum};
+    use std::net::UdpSocket;
+    use std::thread;
+
+    /// A debug link answering the state requests, returns the types of the received messages.
+    fn fake_debug_link(
+        socket: UdpSocket,
+        requests: usize,
+    ) -> thread::JoinHandle<Vec<protos::MessageType>> {
+        thread::spawn(move || {
+            let mut received = Vec::new();
+            let mut buf = [0; 64];
+            while received.len() < requests {
+                let (_, peer) = socket.recv_from(&mut buf).unwrap();
+                // Answer the V2 probe with a V1 `Failure`, like trezor-core does.
+                if buf[..3] != [0x3f, 0x23, 0x23] {
+                    let mut failure = protos::Failure::new();
+                    failure.set_code(protos::Failure_FailureType::Failure_UnexpectedMessage);
+                    let failure =
+                        ProtoMessage(MessageType_Failure, failure.write_to_bytes().unwrap());
+                    socket.send_to(&v1_chunks(failure)[0], peer).unwrap();
+                    continue;
+                }
+                // Only single chunk requests are sent.
+                let message_type = BigEndian::read_u16(&buf[3..5]) as i32;
+                let message_type = protos::MessageType::from_i32(message_type).unwrap();
+                received.push(message_type);
+
+                let resp = match message_type {
+                    MessageType_DebugLinkGetState => {
+                        let mut state = DebugLinkState::new();
+                        state.set_matrix("789456123".to_owned());
+                        state.set_mnemonic_secret(b"all all all".to_vec());
+                        state.set_layout_lines(vec!["Confirm".to_owned()].into());
+                        ProtoMessage(MessageType_DebugLinkState, state.write_to_bytes().unwrap())
+                    }
+                    MessageType_DebugLinkReseedRandom => {
+                        let success = protos::Success::new();
+                        ProtoMessage(MessageType_Success, success.write_to_bytes().unwrap())
+                    }
+                    // Decisions are not answered.
+                    _ => continue,
+                };
+                for chunk in v1_chunks(resp) {
+                    socket.send_to(&chunk, peer).unwrap();
+                }
+            }
+            received
+        })
+    }
+
+    #[test]
+    fn test_debug_link() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = socket.local_addr().unwrap().port() as usize;
+        let device = fake_debug_link(socket, 6);
+
+        let mut transport = UdpTransport::connect(&AvailableDevice {
+            model: TrezorModel::One,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: "127.0.0.1".to_owned(),
+                port,
+            }),
+        })
+        .unwrap();
+        transport.set_config(&TransportConfig {
+            protocol_v2: true,
+            ..TransportConfig::default()
+        });
+        let mut debug = debug_link_with_transport(TrezorModel::One, transport);
+
+        block_on(async {
+            debug.press_yes().await?;
+            assert_eq!(debug.mnemonic().await?, "all all all");
+            // The positions of the digits on the screen.
+            assert_eq!(debug.encode_pin("1234").await?, "7894");
+            assert!(matches!(
+                debug.encode_pin("1230").await,
+                Err(Error::InvalidPin('0'))
+            ));
+            assert_eq!(debug.layout().await?, vec!["Confirm"]);
+            debug.reseed(0).await
+        })
+        .unwrap();
+
+        assert_eq!(
+            device.join().unwrap(),
+            vec![
+                MessageType_DebugLinkDecision,
+                MessageType_DebugLinkGetState,
+                MessageType_DebugLinkGetState,
+                MessageType_DebugLinkGetState,
+                MessageType_DebugLinkGetState,
+                MessageType_DebugLinkReseedRandom,
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! # Entropy health checks
+//!
+//! Basic statistical sanity checks for random data returned by the device,
+//! see [Trezor::get_checked_entropy](crate::Trezor::get_checked_entropy).
+//!
+//! The checks follow the frequency (monobit) and runs tests of NIST SP 800-22.
+//! They detect stuck or heavily biased sources, they don't prove randomness.
+
+use std::fmt;
+
+/// The minimum sample size the checks are meaningful for, in bits.
+pub const MIN_HEALTH_CHECK_BITS: usize = 128;
+
+/// Samples with a p-value below this level are rejected.
+///
+/// Lower than the 0.01 suggested by NIST to keep false alarms rare
+/// when many samples are checked.
+const SIGNIFICANCE_LEVEL: f64 = 0.0001;
+
+/// A failed health check.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum HealthCheck {
+    /// The sample is too short to be checked.
+    SampleTooShort,
+    /// The proportion of ones and zeros is too far from one half.
+    Monobit,
+    /// The number of runs of identical bits is too far from the expected one.
+    Runs,
+}
+
+impl fmt::Display for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthCheck::SampleTooShort => write!(
+                f,
+                "sample is shorter than {} bits",
+                MIN_HEALTH_CHECK_BITS
+            ),
+            HealthCheck::Monobit => write!(f, "monobit test failed"),
+            HealthCheck::Runs => write!(f, "runs test failed"),
+        }
+    }
+}
+
+/// Run the monobit and the runs tests on the given sample.
+pub fn health_check(data: &[u8]) -> Result<(), HealthCheck> {
+    let n = data.len() * 8;
+    if n < MIN_HEALTH_CHECK_BITS {
+        return Err(HealthCheck::SampleTooShort);
+    }
+    let n = n as f64;
+
+    let ones: u32 = data.iter().map(|b| b.count_ones()).sum();
+    let ones = ones as f64;
+
+    // Frequency (monobit) test.
+    let s_obs = (2.0 * ones - n).abs() / n.sqrt();
+    if erfc(s_obs / 2f64.sqrt()) < SIGNIFICANCE_LEVEL {
+        return Err(HealthCheck::Monobit);
+    }
+
+    // Runs test. Its prerequisite in NIST, `|pi - 1/2| < 2 / sqrt(n)`, holds for any
+    // sample passing the frequency test.
+    let pi = ones / n;
+    let runs = 1 + bits(data)
+        .zip(bits(data).skip(1))
+        .filter(|(a, b)| a != b)
+        .count();
+    let expected = 2.0 * n * pi * (1.0 - pi);
+    let deviation = (runs as f64 - expected).abs() / (2.0 * (2.0 * n).sqrt() * pi * (1.0 - pi));
+    if erfc(deviation) < SIGNIFICANCE_LEVEL {
+        return Err(HealthCheck::Runs);
+    }
+
+    Ok(())
+}
+
+/// Iterate over the bits of the data, most significant bit first.
+fn bits(data: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    data.iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| byte & (1 << i) != 0))
+}
+
+/// The complementary error function.
+///
+/// Uses the rational approximation from Numerical Recipes, the fractional
+/// error is less than 1.2e-7 everywhere.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let ans = t * poly.exp();
+    if x >= 0.0 {
+        ans
+    } else {
+        2.0 - ans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A deterministic xorshift generator, good enough to pass the checks.
+    fn pseudo_random(len: usize) -> Vec<u8> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 32) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_health_check_passes_pseudo_random() {
+        assert_eq!(health_check(&pseudo_random(4096)), Ok(()));
+    }
+
+    #[test]
+    fn test_health_check_rejects_biased() {
+        assert_eq!(health_check(&[0; 64]), Err(HealthCheck::Monobit));
+        assert_eq!(health_check(&[0xff; 64]), Err(HealthCheck::Monobit));
+    }
+
+    #[test]
+    fn test_health_check_rejects_alternating() {
+        assert_eq!(health_check(&[0x55; 64]), Err(HealthCheck::Runs));
+        assert_eq!(health_check(&[0x0f; 64]), Err(HealthCheck::Runs));
+    }
+
+    #[test]
+    fn test_health_check_rejects_short() {
+        assert_eq!(health_check(&[0x5a; 8]), Err(HealthCheck::SampleTooShort));
+    }
+}
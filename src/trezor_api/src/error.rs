@@ -1,17 +1,19 @@
 //! # Error Handling
 
-use std::result;
+use std::{error, fmt, result};
 
 use protobuf::error::ProtobufError;
 
 use crate::client::InteractionType;
-use crate::{protos, transport};
+use crate::{entropy, protos, transport, FirmwareVersion, Requirement};
 
 /// Trezor error.
 #[derive(Debug)]
 pub enum Error {
 	/// Less than one device was plugged in.
 	NoDeviceFound,
+	/// The device can't be found again after a reboot, since it isn't attached over USB.
+	UnsupportedReboot,
 	/// More than one device was plugged in.
 	DeviceNotUnique,
 	/// Transport error connecting to device.
@@ -29,11 +31,24 @@ pub enum Error {
 	/// Error reading or writing protobuf messages.
 	Protobuf(ProtobufError),
 	/// A failure message was returned by the device.
-	FailureResponse(protos::Failure),
+	FailureResponse(DeviceFailure),
+	/// The device runs the Bitcoin-only firmware, which doesn't handle the message.
+	BitcoinOnlyFirmware(protos::MessageType),
+	/// The firmware of the device doesn't handle the message.
+	Unsupported {
+		/// What the message needs from the device.
+		needed: Requirement,
+		/// The version of the firmware.
+		actual: FirmwareVersion,
+	},
 	/// An unexpected interaction request was returned by the device.
 	UnexpectedInteractionRequest(InteractionType),
 	/// Provided entropy is not 32 bytes.
 	InvalidEntropy,
+	/// The device returned no entropy.
+	EmptyEntropy,
+	/// The device returned entropy that failed a statistical health check.
+	EntropyHealthCheck(entropy::HealthCheck),
 	/// The device referenced a non-existing input or output index.
 	TxRequestInvalidIndex(usize),
 	/// The device referenced an unknown TXID.
@@ -44,6 +59,8 @@ pub enum Error {
 	// MalformedTxRequest(protos::TxRequest),
 	/// User provided invalid PSBT.
 	InvalidPsbt(String),
+	/// The PIN has a character which is not on the PIN matrix.
+	InvalidPin(char),
 }
 
 impl From<ProtobufError> for Error {
@@ -52,5 +69,181 @@ impl From<ProtobufError> for Error {
 	}
 }
 
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::NoDeviceFound => write!(f, "no Trezor device found"),
+			Error::UnsupportedReboot => {
+				write!(f, "the device can't be found again after a reboot over its transport")
+			}
+			Error::DeviceNotUnique => write!(f, "more than one Trezor device found"),
+			Error::TransportConnect(e) => write!(f, "failed to connect to the device: {}", e),
+			Error::TransportBeginSession(e) => write!(f, "failed to begin a session: {}", e),
+			Error::TransportEndSession(e) => write!(f, "failed to end the session: {}", e),
+			Error::TransportSendMessage(e) => write!(f, "failed to send a message: {}", e),
+			Error::TransportReceiveMessage(e) => write!(f, "failed to receive a message: {}", e),
+			Error::UnexpectedMessageType(t) => write!(f, "unexpected message type: {:?}", t),
+			Error::Protobuf(e) => write!(f, "protobuf error: {}", e),
+			Error::FailureResponse(failure) => write!(f, "the device failed: {}", failure),
+			Error::BitcoinOnlyFirmware(t) => {
+				write!(f, "the Bitcoin-only firmware doesn't handle {:?}", t)
+			}
+			Error::Unsupported { needed, actual } => {
+				write!(f, "the call needs {}, the device runs firmware {}", needed, actual)
+			}
+			Error::UnexpectedInteractionRequest(i) => {
+				write!(f, "unexpected interaction request: {:?}", i)
+			}
+			Error::InvalidEntropy => write!(f, "the entropy is not 32 bytes"),
+			Error::EmptyEntropy => write!(f, "the device returned no entropy"),
+			Error::EntropyHealthCheck(check) => write!(f, "the device entropy is bad: {}", check),
+			Error::TxRequestInvalidIndex(i) => {
+				write!(f, "the device referenced a non-existing index: {}", i)
+			}
+			Error::TxRequestUnknownTxid(txid) => {
+				write!(f, "the device referenced an unknown txid: {}", hex::encode(txid))
+			}
+			Error::PsbtMissingInputTx(txid) => {
+				write!(f, "the PSBT is missing the input tx {}", hex::encode(txid))
+			}
+			Error::InvalidPsbt(e) => write!(f, "invalid PSBT: {}", e),
+			Error::InvalidPin(c) => write!(f, "the PIN matrix has no {:?}", c),
+		}
+	}
+}
+
+impl error::Error for Error {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		match self {
+			Error::TransportConnect(e)
+			| Error::TransportBeginSession(e)
+			| Error::TransportEndSession(e)
+			| Error::TransportSendMessage(e)
+			| Error::TransportReceiveMessage(e) => Some(e),
+			Error::Protobuf(e) => Some(e),
+			Error::FailureResponse(failure) => Some(failure),
+			_ => None,
+		}
+	}
+}
+
+/// A failure returned by the device, by its `Failure.code`, with its message.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum DeviceFailure {
+	/// The device didn't expect the message.
+	UnexpectedMessage(String),
+	/// The device expected a `ButtonAck`.
+	ButtonExpected(String),
+	/// The data sent to the device was invalid.
+	DataError(String),
+	/// The user cancelled the action on the device.
+	ActionCancelled(String),
+	/// The device expected a `PinMatrixAck`.
+	PinExpected(String),
+	/// The user cancelled the PIN entry.
+	PinCancelled(String),
+	/// The PIN was wrong.
+	PinInvalid(String),
+	/// A signature was invalid.
+	InvalidSignature(String),
+	/// The device failed to process the request.
+	ProcessError(String),
+	/// The funds are not enough for the transaction.
+	NotEnoughFunds(String),
+	/// The device is not initialized.
+	NotInitialized(String),
+	/// The PIN entered twice didn't match.
+	PinMismatch(String),
+	/// The wipe code entered twice didn't match.
+	WipeCodeMismatch(String),
+	/// The session to resume doesn't exist.
+	InvalidSession(String),
+	/// The firmware hit an internal error.
+	FirmwareError(String),
+	/// The failure had no code.
+	Unknown(String),
+}
+
+impl DeviceFailure {
+	/// Get the message sent by the device.
+	pub fn message(&self) -> &str {
+		match self {
+			DeviceFailure::UnexpectedMessage(m)
+			| DeviceFailure::ButtonExpected(m)
+			| DeviceFailure::DataError(m)
+			| DeviceFailure::ActionCancelled(m)
+			| DeviceFailure::PinExpected(m)
+			| DeviceFailure::PinCancelled(m)
+			| DeviceFailure::PinInvalid(m)
+			| DeviceFailure::InvalidSignature(m)
+			| DeviceFailure::ProcessError(m)
+			| DeviceFailure::NotEnoughFunds(m)
+			| DeviceFailure::NotInitialized(m)
+			| DeviceFailure::PinMismatch(m)
+			| DeviceFailure::WipeCodeMismatch(m)
+			| DeviceFailure::InvalidSession(m)
+			| DeviceFailure::FirmwareError(m)
+			| DeviceFailure::Unknown(m) => m,
+		}
+	}
+}
+
+impl From<protos::Failure> for DeviceFailure {
+	fn from(mut failure: protos::Failure) -> DeviceFailure {
+		use protos::Failure_FailureType::*;
+
+		let message = failure.take_message();
+		if !failure.has_code() {
+			return DeviceFailure::Unknown(message);
+		}
+		match failure.get_code() {
+			Failure_UnexpectedMessage => DeviceFailure::UnexpectedMessage(message),
+			Failure_ButtonExpected => DeviceFailure::ButtonExpected(message),
+			Failure_DataError => DeviceFailure::DataError(message),
+			Failure_ActionCancelled => DeviceFailure::ActionCancelled(message),
+			Failure_PinExpected => DeviceFailure::PinExpected(message),
+			Failure_PinCancelled => DeviceFailure::PinCancelled(message),
+			Failure_PinInvalid => DeviceFailure::PinInvalid(message),
+			Failure_InvalidSignature => DeviceFailure::InvalidSignature(message),
+			Failure_ProcessError => DeviceFailure::ProcessError(message),
+			Failure_NotEnoughFunds => DeviceFailure::NotEnoughFunds(message),
+			Failure_NotInitialized => DeviceFailure::NotInitialized(message),
+			Failure_PinMismatch => DeviceFailure::PinMismatch(message),
+			Failure_WipeCodeMismatch => DeviceFailure::WipeCodeMismatch(message),
+			Failure_InvalidSession => DeviceFailure::InvalidSession(message),
+			Failure_FirmwareError => DeviceFailure::FirmwareError(message),
+		}
+	}
+}
+
+impl fmt::Display for DeviceFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let kind = match self {
+			DeviceFailure::UnexpectedMessage(_) => "unexpected message",
+			DeviceFailure::ButtonExpected(_) => "button expected",
+			DeviceFailure::DataError(_) => "data error",
+			DeviceFailure::ActionCancelled(_) => "action cancelled",
+			DeviceFailure::PinExpected(_) => "PIN expected",
+			DeviceFailure::PinCancelled(_) => "PIN cancelled",
+			DeviceFailure::PinInvalid(_) => "invalid PIN",
+			DeviceFailure::InvalidSignature(_) => "invalid signature",
+			DeviceFailure::ProcessError(_) => "process error",
+			DeviceFailure::NotEnoughFunds(_) => "not enough funds",
+			DeviceFailure::NotInitialized(_) => "not initialized",
+			DeviceFailure::PinMismatch(_) => "PIN mismatch",
+			DeviceFailure::WipeCodeMismatch(_) => "wipe code mismatch",
+			DeviceFailure::InvalidSession(_) => "invalid session",
+			DeviceFailure::FirmwareError(_) => "firmware error",
+			DeviceFailure::Unknown(_) => "failure",
+		};
+		match self.message() {
+			"" => write!(f, "{}", kind),
+			message => write!(f, "{}: {}", kind, message),
+		}
+	}
+}
+
+impl error::Error for DeviceFailure {}
+
 /// Result type used in this crate.
 pub type Result<T> = result::Result<T, Error>;
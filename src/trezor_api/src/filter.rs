@@ -0,0 +1,381 @@
+//! # Device selection
+//!
+//! With several devices plugged in, [DeviceFilter] picks the wanted one out of the
+//! available devices by its serial number, `Features` or model.
+
+use crate::protos::Features;
+use crate::reboot::DeviceIdentity;
+use crate::transport::Transport;
+use crate::{client, find_devices, transport, AvailableDevice, Error, Result, Trezor, TrezorModel};
+
+/// The outcome of connecting to a device.
+type Connection = std::result::Result<Box<dyn Transport>, transport::Error>;
+
+/// Connects to a device, [transport::connect] outside of the tests.
+type Connect<'a> = &'a (dyn Fn(&AvailableDevice) -> Connection + Sync);
+
+/// A matching device, with the client that read its `Features` if any.
+struct Selected {
+    device: AvailableDevice,
+    trezor: Option<Trezor>,
+}
+
+impl Selected {
+    /// Close the client and get the device.
+    async fn release(self) -> AvailableDevice {
+        if let Some(trezor) = self.trezor {
+            if let Err(e) = trezor.close().await {
+                log::warn!("failed to close {}: {}", self.device, e);
+            }
+        }
+        self.device
+    }
+
+    /// Get a client for the device, the one that read its `Features` if any.
+    async fn connect(self) -> Result<Trezor> {
+        match self.trezor {
+            Some(mut trezor) => {
+                // The client is handed out like a new one, to be initialized.
+                trezor.session_end().await?;
+                trezor.identity = Some(DeviceIdentity::of(&self.device));
+                Ok(trezor)
+            }
+            None => self.device.connect(),
+        }
+    }
+}
+
+/// Close the clients and get the devices.
+async fn release(selected: Vec<Selected>) -> Vec<AvailableDevice> {
+    let mut devices = Vec::with_capacity(selected.len());
+    for s in selected {
+        devices.push(s.release().await);
+    }
+    devices
+}
+
+/// The outcome of matching the devices.
+struct Selection {
+    devices: Vec<Selected>,
+    /// Whether the serial number was missing on any device.
+    missing_serial: bool,
+    /// The first error from the devices that couldn't be checked.
+    error: Option<Error>,
+}
+
+/// Criteria the wanted devices must all match, the unset ones match any device.
+///
+/// Matching by `device_id` or `label` connects to the devices to read their
+/// `Features`, so the devices must not be in use.
+#[derive(Debug, Default, Clone)]
+pub struct DeviceFilter {
+    /// The model of the device.
+    pub model: Option<TrezorModel>,
+    /// The USB serial number, see [AvailableDeviceTransport::serial_number].
+    ///
+    /// [AvailableDeviceTransport::serial_number]: transport::AvailableDeviceTransport::serial_number
+    pub serial_number: Option<String>,
+    /// The `device_id` reported in the `Features`.
+    pub device_id: Option<String>,
+    /// The `label` reported in the `Features`.
+    pub label: Option<String>,
+}
+
+impl DeviceFilter {
+    /// Match the device with the given USB serial number.
+    pub fn serial_number(serial_number: &str) -> DeviceFilter {
+        DeviceFilter {
+            serial_number: Some(serial_number.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Match the device with the given `device_id`.
+    pub fn device_id(device_id: &str) -> DeviceFilter {
+        DeviceFilter {
+            device_id: Some(device_id.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Keep the devices matching the filter.
+    ///
+    /// The devices that fail to connect or initialize are skipped, their error is
+    /// returned only if no device matches.
+    pub async fn select(&self, devices: Vec<AvailableDevice>) -> Result<Vec<AvailableDevice>> {
+        let selection = self.select_inner(devices, &transport::connect).await;
+        match selection.error {
+            Some(e) if selection.devices.is_empty() => Err(e),
+            _ => Ok(release(selection.devices).await),
+        }
+    }
+
+    /// Pick the single device matching the filter.
+    ///
+    /// Fails with [Error::NoDeviceFound] or [Error::DeviceNotUnique] if there is
+    /// no or more than one such device. When matching by the serial number and
+    /// some devices didn't report one, no match fails with
+    /// [transport::Error::NoDeviceSerial] instead. If some devices failed to connect
+    /// or initialize, no match fails with the error of the first one.
+    pub async fn select_unique(&self, devices: Vec<AvailableDevice>) -> Result<AvailableDevice> {
+        let selected = self
+            .select_unique_inner(devices, &transport::connect)
+            .await?;
+        Ok(selected.release().await)
+    }
+
+    async fn select_unique_inner(
+        &self,
+        devices: Vec<AvailableDevice>,
+        connect: Connect<'_>,
+    ) -> Result<Selected> {
+        let mut selection = self.select_inner(devices, connect).await;
+        match selection.devices.len() {
+            0 => Err(selection.error.unwrap_or(match selection.missing_serial {
+                true => Error::TransportConnect(transport::Error::NoDeviceSerial),
+                false => Error::NoDeviceFound,
+            })),
+            1 => Ok(selection.devices.remove(0)),
+            _ => {
+                release(selection.devices).await;
+                Err(Error::DeviceNotUnique)
+            }
+        }
+    }
+
+    async fn select_inner(&self, devices: Vec<AvailableDevice>, connect: Connect<'_>) -> Selection {
+        let mut selection = Selection {
+            devices: Vec::new(),
+            missing_serial: false,
+            error: None,
+        };
+        for device in devices {
+            if self.model.iter().any(|&model| model != device.model) {
+                continue;
+            }
+            if let Some(ref serial_number) = self.serial_number {
+                match device.transport.serial_number() {
+                    Ok(serial) if serial == serial_number => {}
+                    Ok(_) => continue,
+                    Err(_) => {
+                        selection.missing_serial = true;
+                        continue;
+                    }
+                }
+            }
+            let mut trezor = None;
+            if self.device_id.is_some() || self.label.is_some() {
+                // The debug link doesn't report `Features`.
+                if device.debug {
+                    continue;
+                }
+                match self.matches_features(&device, connect).await {
+                    Ok(Some(t)) => trezor = Some(t),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::warn!("skipping {}: {}", device, e);
+                        selection.error.get_or_insert(e);
+                        continue;
+                    }
+                }
+            }
+            selection.devices.push(Selected { device, trezor });
+        }
+        selection
+    }
+
+    /// Connect to the device and compare its `Features`, the client is kept if they match.
+    ///
+    /// The `Features` are read with `GetFeatures` where the firmware has it, so the
+    /// sessions of other clients of the device aren't lost.
+    async fn matches_features(
+        &self,
+        device: &AvailableDevice,
+        connect: Connect<'_>,
+    ) -> Result<Option<Trezor>> {
+        let transport = connect(device).map_err(Error::TransportConnect)?;
+        let mut trezor = client::trezor_with_transport(device.model, transport);
+        let probe = trezor.probe_features().await;
+        let matches = trezor.features().map(|f| self.matches(f)).unwrap_or(false);
+        if probe.is_ok() && matches {
+            return Ok(Some(trezor));
+        }
+        let close = trezor.close().await;
+        probe?;
+        close?;
+        Ok(None)
+    }
+
+    fn matches(&self, features: &Features) -> bool {
+        self.device_id
+            .iter()
+            .all(|id| id == features.get_device_id())
+            && self.label.iter().all(|label| label == features.get_label())
+    }
+}
+
+/// Find the Trezor devices matching the filter.
+pub async fn find_device(filter: &DeviceFilter) -> Result<Vec<AvailableDevice>> {
+    filter.select(find_devices().await?).await
+}
+
+/// Connect to the single Trezor device matching the filter.
+///
+/// Fails if there is no or more than one such device, see [DeviceFilter::select_unique].
+/// The connection used to read the `Features` of the device is kept.
+pub async fn connect_unique(filter: &DeviceFilter) -> Result<Trezor> {
+    let devices = find_devices().await?;
+    let selected = filter
+        .select_unique_inner(devices, &transport::connect)
+        .await?;
+    selected.connect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos::{self, MessageType};
+    use crate::transport::mock::{MockTransport, Script};
+    use crate::transport::udp::AvailableUdpTransport;
+    use crate::transport::AvailableDeviceTransport;
+    use crate::DeviceMode;
+    use futures::executor::block_on;
+
+    fn udp_device(port: usize) -> AvailableDevice {
+        AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: "127.0.0.1".to_owned(),
+                port,
+            }),
+        }
+    }
+
+    fn features(label: &str) -> protos::Features {
+        let mut features = protos::Features::new();
+        features.set_major_version(2);
+        features.set_minor_version(4);
+        features.set_patch_version(0);
+        features.set_label(label.to_owned());
+        features
+    }
+
+    /// The first device can't be connected to, the second fails to report its
+    /// `Features`, the fourth has no `GetFeatures`.
+    fn connect(device: &AvailableDevice) -> Connection {
+        let script = Script::new().expect_type(MessageType::MessageType_GetFeatures);
+        let script = match device.id().as_str() {
+            "udp:127.0.0.1:1" => return Err(transport::Error::DeviceDisconnected),
+            "udp:127.0.0.1:2" => script.respond(protos::Failure::new()),
+            "udp:127.0.0.1:4" => {
+                let mut failure = protos::Failure::new();
+                failure.set_code(protos::Failure_FailureType::Failure_UnexpectedMessage);
+                script
+                    .respond(failure)
+                    .expect_type(MessageType::MessageType_Initialize)
+                    .respond(features("Carol"))
+            }
+            _ => script.respond(features("Bob")),
+        };
+        Ok(Box::new(MockTransport::new(script)))
+    }
+
+    fn devices() -> Vec<AvailableDevice> {
+        (1..5).map(udp_device).collect()
+    }
+
+    #[test]
+    fn test_select_skips_failing_devices() {
+        let filter = DeviceFilter {
+            label: Some("Bob".to_owned()),
+            ..Default::default()
+        };
+        let selected = block_on(filter.select_unique_inner(devices(), &connect)).unwrap();
+        assert_eq!(selected.device.id(), "udp:127.0.0.1:3");
+
+        // Without a match, the error of the first failing device is reported.
+        let filter = DeviceFilter {
+            label: Some("Alice".to_owned()),
+            ..Default::default()
+        };
+        let res = block_on(filter.select_unique_inner(devices(), &connect));
+        assert!(matches!(
+            res,
+            Err(Error::TransportConnect(
+                transport::Error::DeviceDisconnected
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_select_without_get_features() {
+        let filter = DeviceFilter {
+            label: Some("Carol".to_owned()),
+            ..Default::default()
+        };
+        let selected = block_on(filter.select_unique_inner(devices(), &connect)).unwrap();
+        assert_eq!(selected.device.id(), "udp:127.0.0.1:4");
+    }
+
+    #[test]
+    fn test_connect_keeps_probed_client() {
+        let filter = DeviceFilter {
+            label: Some("Bob".to_owned()),
+            ..Default::default()
+        };
+        let selected = block_on(filter.select_unique_inner(devices(), &connect)).unwrap();
+        let trezor = block_on(selected.connect()).unwrap();
+        assert_eq!(trezor.features().unwrap().get_label(), "Bob");
+        assert!(trezor.identity.is_some());
+    }
+
+    #[cfg(feature = "simulator")]
+    fn simulator(label: &str) -> crate::simulator::SimulatorHandle {
+        use crate::simulator::{Simulator, SimulatorConfig};
+
+        let config = SimulatorConfig {
+            label: label.to_owned(),
+            ..Default::default()
+        };
+        Simulator::bind("127.0.0.1:0", config).unwrap().spawn()
+    }
+
+    #[cfg(feature = "simulator")]
+    #[test]
+    fn test_select_unique() {
+        let simulators = [simulator("Alice"), simulator("Bob")];
+        let devices = || {
+            simulators
+                .iter()
+                .map(|s| s.available_device().unwrap())
+                .collect()
+        };
+
+        let filter = DeviceFilter {
+            label: Some("Bob".to_owned()),
+            ..Default::default()
+        };
+        let device = block_on(filter.select_unique(devices())).unwrap();
+        assert_eq!(device.id(), simulators[1].available_device().unwrap().id());
+
+        // Both simulators report the same device id.
+        let res = block_on(DeviceFilter::device_id("SIMULATOR").select_unique(devices()));
+        assert!(matches!(res, Err(Error::DeviceNotUnique)));
+
+        let filter = DeviceFilter {
+            model: Some(TrezorModel::One),
+            ..Default::default()
+        };
+        let res = block_on(filter.select_unique(devices()));
+        assert!(matches!(res, Err(Error::NoDeviceFound)));
+
+        // UDP devices have no serial number.
+        let res = block_on(DeviceFilter::serial_number("1234").select_unique(devices()));
+        assert!(matches!(
+            res,
+            Err(Error::TransportConnect(transport::Error::NoDeviceSerial))
+        ));
+    }
+}
@@ -9,22 +9,21 @@
 //! After you have a [Trezor] instance, you need to initialize it with
 //! [Trezor::init_device] before you can interact with the device.
 //!
+//! With several devices plugged in, [connect_unique] connects to the one
+//! matching a [DeviceFilter].
+//!
 //! # Example
 //! ```no_run
-//! # use trezor_api::find_devices;
-//!
-//! let mut devices = find_devices().unwrap();
-//!
-//! // take the first device out of devices.
-//! let device = devices.remove(0);
-//!
-//! let mut trezor = device.connect().unwrap();
-//! trezor.init_device().unwrap();
+//! # use trezor_api::{connect_unique, DeviceFilter};
+//! # futures::executor::block_on(async {
+//! let mut trezor = connect_unique(&DeviceFilter::device_id("2E8B9D0F11C34A87")).await.unwrap();
+//! trezor.init_device().await.unwrap();
 //!
 //! // After this you can interact with Trezor device.
 //! let address = trezor.get_address(
 //!     &"m/44'/1729'/0'/0'".parse().unwrap(),
-//! ).unwrap().ack_all().unwrap();
+//! ).await.unwrap().ack_all().await.unwrap();
+//! # });
 //! ```
 //!
 //! # Interacting with Trezor
@@ -58,6 +57,7 @@ extern crate serde_derive;
 
 use std::fmt;
 
+pub mod entropy;
 pub mod messages;
 pub mod protos;
 
@@ -73,9 +73,36 @@ mod client;
 
 pub use client::*;
 
+mod debug_link;
+
+pub use debug_link::*;
+
+pub mod logging;
+
+mod time;
+
+mod watch;
+
+pub use watch::*;
+
+mod filter;
+
+pub use filter::*;
+
+mod support;
+
+pub use support::*;
+
+mod reboot;
+
+pub use reboot::*;
+
+#[cfg(all(feature = "simulator", not(target_arch = "wasm32")))]
+pub mod simulator;
+
 pub const DEV_TREZOR_ONE: (u16, u16) = (0x534C, 0x0001);
 pub const DEV_TREZOR_T: (u16, u16) = (0x1209, 0x53C1);
-// pub const DEV_TREZOR_T_BL: (u16, u16) = (0x1209, 0x53C0);
+pub const DEV_TREZOR_T_BL: (u16, u16) = (0x1209, 0x53C0);
 pub const CONFIG_ID: u8 = 0;
 pub const INTERFACE_DESCRIPTOR: u8 = 0;
 pub const LIBUSB_CLASS_VENDOR_SPEC: u8 = 0xff;
@@ -115,6 +142,7 @@ macro_rules! console_log {
 #[derive(Debug)]
 pub struct AvailableDevice {
     pub model: TrezorModel,
+    pub mode: DeviceMode,
     pub debug: bool,
     pub transport: AvailableDeviceTransport,
 }
@@ -123,8 +151,8 @@ impl fmt::Display for AvailableDevice {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{} (transport: {}) (debug: {})",
-            self.model, &self.transport, self.debug
+            "{} (mode: {}) (transport: {}) (debug: {})",
+            self.model, self.mode, &self.transport, self.debug
         )
     }
 }
@@ -137,10 +165,33 @@ impl AvailableDevice {
         self.debug = true;
     }
 
+    /// An identity of the device that stays the same across enumerations, see
+    /// [watch_devices].
+    ///
+    /// The debug link of a device has its own identity. Without a USB serial number,
+    /// f.e. on Trezor Model T, the identity changes when the device is plugged in
+    /// again, see [AvailableDevice::has_stable_id].
+    pub fn id(&self) -> String {
+        match self.debug {
+            false => self.transport.id(),
+            true => format!("{}/debug", self.transport.id()),
+        }
+    }
+
+    /// Whether [AvailableDevice::id] stays the same when the device is plugged in again.
+    ///
+    /// Read `Features::device_id` after connecting to recognize the devices without
+    /// a stable identity.
+    pub fn has_stable_id(&self) -> bool {
+        self.transport.has_stable_id()
+    }
+
     /// Connect to the device.
     pub fn connect(self) -> Result<Trezor> {
         let t = transport::connect(&self).map_err(|e| Error::TransportConnect(e))?;
-        Ok(client::trezor_with_transport(self.model, t))
+        let mut trezor = client::trezor_with_transport(self.model, t);
+        trezor.identity = Some(DeviceIdentity::of(&self));
+        Ok(trezor)
     }
 }
 
@@ -153,14 +204,18 @@ pub enum TrezorModel {
 }
 
 impl TrezorModel {
-    #[cfg(not(target_arch = "wasm32"))]
-    pub fn from_device_descriptor(desc: &rusb::DeviceDescriptor) -> Option<Self> {
-        match (desc.vendor_id(), desc.product_id()) {
+    pub fn from_usb_ids(vendor_id: u16, product_id: u16) -> Option<Self> {
+        match (vendor_id, product_id) {
             DEV_TREZOR_ONE => Some(TrezorModel::One),
-            DEV_TREZOR_T => Some(TrezorModel::T),
+            DEV_TREZOR_T | DEV_TREZOR_T_BL => Some(TrezorModel::T),
             _ => None,
         }
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_device_descriptor(desc: &rusb::DeviceDescriptor) -> Option<Self> {
+        Self::from_usb_ids(desc.vendor_id(), desc.product_id())
+    }
 }
 
 impl fmt::Display for TrezorModel {
@@ -173,13 +228,78 @@ impl fmt::Display for TrezorModel {
     }
 }
 
+/// The state the device was enumerated in.
+///
+/// Trezor Model T re-enumerates with a different product id when it
+/// boots into the bootloader, so the mode can be told apart without
+/// talking to the device.  Trezor Model One keeps its ids in the older
+/// bootloaders and is reported as [DeviceMode::Normal] there, while the
+/// newer ones share the product id of the Trezor Model T bootloader and
+/// are reported as a Trezor Model T.  Check `Features::get_bootloader_mode`
+/// and `Features::get_model` after the initialization to be sure.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DeviceMode {
+    /// The device runs a firmware.
+    Normal,
+    /// The device runs the bootloader.
+    Bootloader,
+}
+
+impl DeviceMode {
+    pub fn from_usb_ids(vendor_id: u16, product_id: u16) -> Self {
+        match (vendor_id, product_id) {
+            DEV_TREZOR_T_BL => DeviceMode::Bootloader,
+            _ => DeviceMode::Normal,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_device_descriptor(desc: &rusb::DeviceDescriptor) -> Self {
+        Self::from_usb_ids(desc.vendor_id(), desc.product_id())
+    }
+}
+
+impl fmt::Display for DeviceMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceMode::Normal => write!(f, "normal"),
+            DeviceMode::Bootloader => write!(f, "bootloader"),
+        }
+    }
+}
+
+/// The devices listed by Trezor Bridge, `None` if it isn't running.
+///
+/// The Bridge lists every device plugged in and holds the ones in use, so its devices
+/// replace the directly found ones instead of being added to them.
+#[cfg(feature = "bridge")]
+async fn find_bridge_devices() -> Option<Vec<AvailableDevice>> {
+    match transport::bridge::BridgeTransport::find_devices().await {
+        Ok(devices) => Some(devices),
+        Err(e) => {
+            log::debug!("not listing the devices through the Bridge: {}", e);
+            None
+        }
+    }
+}
+
 /// Find Trezor devices.
+///
+/// With the `bridge` feature, the devices are listed through Trezor Bridge when it is
+/// running, see [transport::bridge::BridgeTransport::find_devices].
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn find_devices() -> Result<Vec<AvailableDevice>> {
     use transport::usb::UsbTransport;
 
+    #[cfg(feature = "bridge")]
+    if let Some(devices) = find_bridge_devices().await {
+        return Ok(devices);
+    }
     let mut devices = Vec::new();
     devices.extend(UsbTransport::find_devices().map_err(|e| Error::TransportConnect(e))?);
+    // The USB transport skips the HID interfaces of legacy firmwares.
+    #[cfg(feature = "hid")]
+    devices.extend(transport::hid::HidTransport::find_devices().map_err(Error::TransportConnect)?);
     Ok(devices)
 }
 
@@ -187,6 +307,10 @@ pub async fn find_devices() -> Result<Vec<AvailableDevice>> {
 pub async fn find_devices() -> Result<Vec<AvailableDevice>> {
     use transport::webusb::WebUsbTransport;
 
+    #[cfg(feature = "bridge")]
+    if let Some(devices) = find_bridge_devices().await {
+        return Ok(devices);
+    }
     WebUsbTransport::find_devices()
         .await
         .map_err(|e| Error::TransportConnect(e))
@@ -0,0 +1,162 @@
+//! # Logging
+//!
+//! The crate logs through the `log` facade, install any logger to see the output. The
+//! calls and the messages exchanged are logged at `debug` level, the protocol and
+//! transport details at `trace` level. Every call gets an id, which the lines about
+//! the call start with.
+//!
+//! The payloads are logged decoded at `trace` level, except the ones carrying secrets like
+//! PINs, passphrases, mnemonics, entropy and keys, which are always redacted.
+//!
+//! In the browser, [init_console_logger] sends the logs to the console.
+
+use std::fmt;
+
+use crate::messages::AnyTrezorMessage;
+use crate::protos::MessageType::{self, *};
+use crate::transport::ProtoMessage;
+
+/// The messages carrying secrets, their payloads are never logged.
+const SECRET_MESSAGE_TYPES: &[MessageType] = &[
+    MessageType_PinMatrixAck,
+    MessageType_PassphraseAck,
+    MessageType_WordAck,
+    MessageType_Entropy,
+    MessageType_EntropyAck,
+    MessageType_LoadDevice,
+    MessageType_CipherKeyValue,
+    MessageType_CipheredKeyValue,
+    MessageType_DebugLinkDecision,
+    MessageType_DebugLinkState,
+    MessageType_DebugLinkLayout,
+    MessageType_DebugLinkMemory,
+    MessageType_DebugLinkMemoryWrite,
+    MessageType_ECDHSessionKey,
+    MessageType_MoneroWatchKey,
+    MessageType_MoneroGetTxKeyAck,
+    MessageType_MoneroKeyImageSyncStepAck,
+    MessageType_MoneroKeyImageSyncFinalAck,
+    MessageType_MoneroLiveRefreshStepAck,
+    MessageType_MoneroTransactionFinalAck,
+    MessageType_NEMDecryptedMessage,
+];
+
+/// Whether the payloads of the given type carry secrets.
+pub fn is_secret(message_type: MessageType) -> bool {
+    SECRET_MESSAGE_TYPES.contains(&message_type)
+}
+
+/// Formats the payload of a message for the logs, redacting the secrets.
+///
+/// Only the length of the payloads which don't decode is shown, since they may be
+/// of a type carrying secrets the crate doesn't know.
+pub(crate) struct Payload<'a>(pub &'a ProtoMessage);
+
+impl fmt::Display for Payload<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match is_secret(self.0.message_type()) {
+            true => write!(f, "<redacted>"),
+            false => match AnyTrezorMessage::decode(self.0.clone()) {
+                Ok(AnyTrezorMessage::Unknown(_)) | Err(_) => {
+                    write!(f, "<{} bytes>", self.0.payload().len())
+                }
+                Ok(message) => write!(f, "{:?}", message),
+            },
+        }
+    }
+}
+
+/// Send the logs of at most the given level to the browser console.
+///
+/// Fails if a logger was installed already.
+#[cfg(target_arch = "wasm32")]
+pub fn init_console_logger(level: log::LevelFilter) -> Result<(), log::SetLoggerError> {
+    log::set_logger(&ConsoleLogger)?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+/// A logger writing to the browser console.
+#[cfg(target_arch = "wasm32")]
+struct ConsoleLogger;
+
+#[cfg(target_arch = "wasm32")]
+impl log::Log for ConsoleLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        use web_sys::console;
+
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let msg = wasm_bindgen::JsValue::from(format!("{}] {}", record.target(), record.args()));
+        match record.level() {
+            log::Level::Error => console::error_1(&msg),
+            log::Level::Warn => console::warn_1(&msg),
+            log::Level::Info => console::info_1(&msg),
+            log::Level::Debug | log::Level::Trace => console::debug_1(&msg),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_redaction() {
+        let pin = ProtoMessage(MessageType_PinMatrixAck, b"\x0a\x041234".to_vec());
+        assert_eq!(Payload(&pin).to_string(), "<redacted>");
+
+        let ping = ProtoMessage(MessageType_Ping, vec![0x0a, 0x01, 0x61]);
+        assert_eq!(Payload(&ping).to_string(), "Ping(message: \"a\")");
+
+        // The types without a proto are not printed as hex.
+        let unknown = ProtoMessage(MessageType_EthereumGetAddress, vec![0x08, 0x01]);
+        assert_eq!(Payload(&unknown).to_string(), "<2 bytes>");
+        let invalid = ProtoMessage(MessageType_Ping, vec![0xff, 0xff]);
+        assert_eq!(Payload(&invalid).to_string(), "<2 bytes>");
+    }
+
+    #[test]
+    fn test_secret_message_types() {
+        let secret = [
+            MessageType_PinMatrixAck,
+            MessageType_PassphraseAck,
+            MessageType_WordAck,
+            MessageType_Entropy,
+            MessageType_EntropyAck,
+            MessageType_LoadDevice,
+            MessageType_CipherKeyValue,
+            MessageType_CipheredKeyValue,
+            MessageType_DebugLinkDecision,
+            MessageType_DebugLinkState,
+            MessageType_DebugLinkLayout,
+            MessageType_DebugLinkMemory,
+            MessageType_DebugLinkMemoryWrite,
+            MessageType_ECDHSessionKey,
+            MessageType_MoneroWatchKey,
+            MessageType_MoneroGetTxKeyAck,
+            MessageType_MoneroKeyImageSyncStepAck,
+            MessageType_MoneroKeyImageSyncFinalAck,
+            MessageType_MoneroLiveRefreshStepAck,
+            MessageType_MoneroTransactionFinalAck,
+            MessageType_NEMDecryptedMessage,
+        ];
+        for &message_type in &secret {
+            let message = ProtoMessage(message_type, vec![0x0a, 0x01, 0x61]);
+            assert_eq!(
+                Payload(&message).to_string(),
+                "<redacted>",
+                "{:?}",
+                message_type
+            );
+        }
+        assert_eq!(SECRET_MESSAGE_TYPES.len(), secret.len());
+    }
+}
@@ -1,65 +1,156 @@
 use protobuf;
+use protobuf::error::ProtobufError;
 
 use crate::protos::MessageType::*;
 use crate::protos::*;
+use crate::transport::ProtoMessage;
 
 ///! In this module we implement the message_type() getter for all protobuf message types.
 
 /// This trait extends the protobuf Message trait to also have a static getter for the message
-/// type code.  This getter is implemented in this file for all the messages we use.
+/// type code.  This getter is implemented for all the messages named like a `MessageType`
+/// entry, see `build.rs`.
 pub trait TrezorMessage: protobuf::Message {
     fn message_type() -> MessageType;
 }
 
-/// This macro provides the TrezorMessage trait for a protobuf message.
+/// A message the host sends to the device, marked `wire_in` in the protos.
+///
+/// Only these can be sent with [Trezor::call](crate::Trezor::call):
+///
+/// ```compile_fail
+/// # async fn send_features(trezor: &mut trezor_api::Trezor) {
+/// trezor.call_raw(trezor_api::protos::Features::new()).await;
+/// # }
+/// ```
+pub trait HostToDevice: TrezorMessage {}
+
+/// A message the device sends to the host, marked `wire_out` in the protos.
+pub trait DeviceToHost: TrezorMessage {}
+
+/// A message only exchanged over the debug link, marked `wire_debug_in` or
+/// `wire_debug_out` in the protos.
+pub trait DebugOnly: TrezorMessage {}
+
+/// A message only handled by the bootloader, marked `wire_bootloader` in the protos.
+pub trait BootloaderOnly: TrezorMessage {}
+
+/// This macro provides the TrezorMessage trait and the given marker traits for a
+/// protobuf message.
 macro_rules! trezor_message_impl {
-	($struct:ident, $mtype:expr) => {
+	($struct:ident, $mtype:expr $(, $marker:ident)*) => {
 		impl TrezorMessage for $struct {
 			fn message_type() -> MessageType {
 				$mtype
 			}
 		}
+		$(impl $marker for $struct {})*
+	};
+}
+
+/// This macro defines the AnyTrezorMessage enum over the given messages and their types.
+macro_rules! any_trezor_message {
+	($($struct:ident => $mtype:ident,)*) => {
+		/// Any Trezor message, decoded by its type.
+		#[derive(Debug, Clone, PartialEq)]
+		#[allow(non_camel_case_types)]
+		pub enum AnyTrezorMessage {
+			$($struct($struct),)*
+			/// A message of a type without a definition in the protos.
+			Unknown(ProtoMessage),
+		}
+
+		impl AnyTrezorMessage {
+			/// Decode a message into the struct of its type.
+			pub fn decode(message: ProtoMessage) -> Result<AnyTrezorMessage, ProtobufError> {
+				Ok(match message.message_type() {
+					$($mtype => AnyTrezorMessage::$struct(message.into_message()?),)*
+					_ => AnyTrezorMessage::Unknown(message),
+				})
+			}
+
+			pub fn message_type(&self) -> MessageType {
+				match self {
+					$(AnyTrezorMessage::$struct(_) => $mtype,)*
+					AnyTrezorMessage::Unknown(message) => message.message_type(),
+				}
+			}
+
+			/// Encode the message back to be sent over a transport.
+			pub fn encode(&self) -> Result<ProtoMessage, ProtobufError> {
+				match self {
+					$(AnyTrezorMessage::$struct(m) => Ok(ProtoMessage($mtype, protobuf::Message::write_to_bytes(m)?)),)*
+					AnyTrezorMessage::Unknown(message) => Ok(message.clone()),
+				}
+			}
+		}
 	};
 }
 
-trezor_message_impl!(Initialize, MessageType_Initialize);
-trezor_message_impl!(Ping, MessageType_Ping);
-trezor_message_impl!(Success, MessageType_Success);
-trezor_message_impl!(Failure, MessageType_Failure);
-trezor_message_impl!(ChangePin, MessageType_ChangePin);
-trezor_message_impl!(WipeDevice, MessageType_WipeDevice);
-trezor_message_impl!(GetEntropy, MessageType_GetEntropy);
-trezor_message_impl!(Entropy, MessageType_Entropy);
-trezor_message_impl!(LoadDevice, MessageType_LoadDevice);
-trezor_message_impl!(ResetDevice, MessageType_ResetDevice);
-trezor_message_impl!(Features, MessageType_Features);
-trezor_message_impl!(PinMatrixRequest, MessageType_PinMatrixRequest);
-trezor_message_impl!(PinMatrixAck, MessageType_PinMatrixAck);
-trezor_message_impl!(Cancel, MessageType_Cancel);
-trezor_message_impl!(EndSession, MessageType_EndSession);
-trezor_message_impl!(ApplySettings, MessageType_ApplySettings);
-trezor_message_impl!(ButtonRequest, MessageType_ButtonRequest);
-trezor_message_impl!(ButtonAck, MessageType_ButtonAck);
-trezor_message_impl!(ApplyFlags, MessageType_ApplyFlags);
-trezor_message_impl!(BackupDevice, MessageType_BackupDevice);
-trezor_message_impl!(EntropyRequest, MessageType_EntropyRequest);
-trezor_message_impl!(EntropyAck, MessageType_EntropyAck);
-trezor_message_impl!(RecoveryDevice, MessageType_RecoveryDevice);
-trezor_message_impl!(WordRequest, MessageType_WordRequest);
-trezor_message_impl!(WordAck, MessageType_WordAck);
-trezor_message_impl!(GetFeatures, MessageType_GetFeatures);
-trezor_message_impl!(SetU2FCounter, MessageType_SetU2FCounter);
-// trezor_message_impl!(FirmwareErase, MessageType_FirmwareErase);
-// trezor_message_impl!(FirmwareUpload, MessageType_FirmwareUpload);
-// trezor_message_impl!(FirmwareRequest, MessageType_FirmwareRequest);
-// trezor_message_impl!(SelfTest, MessageType_SelfTest);
-trezor_message_impl!(TezosGetAddress, MessageType_TezosGetAddress);
-trezor_message_impl!(TezosAddress, MessageType_TezosAddress);
-trezor_message_impl!(TezosSignTx, MessageType_TezosSignTx);
-trezor_message_impl!(TezosSignedTx, MessageType_TezosSignedTx);
-trezor_message_impl!(TezosGetPublicKey, MessageType_TezosGetPublicKey);
-trezor_message_impl!(TezosPublicKey, MessageType_TezosPublicKey);
-// Bitcoin
-trezor_message_impl!(GetAddress, MessageType_GetAddress);
-trezor_message_impl!(Address, MessageType_Address);
+/// This macro defines is_bitcoin_only over the given message types.
+macro_rules! bitcoin_only_message_types {
+	($($mtype:ident,)*) => {
+		/// Whether the Bitcoin-only firmware handles the messages of the type, marked
+		/// `bitcoin_only` in the protos.
+		pub fn is_bitcoin_only(message_type: MessageType) -> bool {
+			matches!(message_type, $($mtype)|*)
+		}
+	};
+}
+
+#[cfg(not(feature = "pure-codegen"))]
+include!("messages_generated.rs");
+#[cfg(feature = "pure-codegen")]
+include!(concat!(env!("OUT_DIR"), "/messages_generated.rs"));
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use protobuf::Message;
 
+	#[test]
+	fn test_any_trezor_message() {
+		let mut ping = Ping::new();
+		ping.set_message("hello".to_owned());
+		let message = ProtoMessage(MessageType_Ping, ping.write_to_bytes().unwrap());
+		let decoded = AnyTrezorMessage::decode(message.clone()).unwrap();
+		assert_eq!(decoded, AnyTrezorMessage::Ping(ping));
+		assert_eq!(decoded.message_type(), MessageType_Ping);
+		assert_eq!(decoded.encode().unwrap(), message);
+
+		// The messages overriding their wire type are decoded as the original one.
+		assert_eq!(TxAckInput::message_type(), MessageType_TxAck);
+		let tx_ack = ProtoMessage(MessageType_TxAck, vec![]);
+		assert!(matches!(AnyTrezorMessage::decode(tx_ack), Ok(AnyTrezorMessage::TxAck(_))));
+
+		let unknown = ProtoMessage(MessageType_EthereumGetAddress, vec![0x08, 0x01]);
+		assert_eq!(AnyTrezorMessage::decode(unknown.clone()).unwrap(), AnyTrezorMessage::Unknown(unknown));
+	}
+
+	#[cfg(feature = "with-serde")]
+	#[test]
+	fn test_serde_json() {
+		let mut features = Features::new();
+		features.set_vendor("trezor.io".to_owned());
+		features.set_major_version(2);
+		features.set_capabilities(vec![Features_Capability::Capability_Bitcoin]);
+		let json = serde_json::to_string(&features).unwrap();
+		assert!(json.contains(r#""vendor":"trezor.io""#));
+		assert!(json.contains(r#""capabilities":["Capability_Bitcoin"]"#));
+		assert_eq!(serde_json::from_str::<Features>(&json).unwrap(), features);
+
+		let mut tx = TezosSignTx::new();
+		tx.set_address_n(vec![0x8000_002c, 0x8000_06c1]);
+		tx.set_branch(vec![1, 2, 3]);
+		let json = serde_json::to_string(&tx).unwrap();
+		assert_eq!(serde_json::from_str::<TezosSignTx>(&json).unwrap(), tx);
+	}
+
+	#[test]
+	fn test_bitcoin_only() {
+		assert!(is_bitcoin_only(MessageType_GetAddress));
+		assert!(is_bitcoin_only(MessageType_DebugLinkGetState));
+		assert!(!is_bitcoin_only(MessageType_EthereumGetAddress));
+		assert!(!is_bitcoin_only(MessageType_TezosGetAddress));
+	}
+}
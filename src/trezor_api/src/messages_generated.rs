@@ -0,0 +1,232 @@
+// This file is generated by build.rs from the protos, do not edit.
+
+trezor_message_impl!(Success, MessageType_Success, DeviceToHost);
+trezor_message_impl!(Failure, MessageType_Failure, DeviceToHost);
+trezor_message_impl!(ButtonRequest, MessageType_ButtonRequest, DeviceToHost);
+trezor_message_impl!(ButtonAck, MessageType_ButtonAck, HostToDevice);
+trezor_message_impl!(PinMatrixRequest, MessageType_PinMatrixRequest, DeviceToHost);
+trezor_message_impl!(PinMatrixAck, MessageType_PinMatrixAck, HostToDevice);
+trezor_message_impl!(PassphraseRequest, MessageType_PassphraseRequest, DeviceToHost);
+trezor_message_impl!(PassphraseAck, MessageType_PassphraseAck, HostToDevice);
+trezor_message_impl!(Deprecated_PassphraseStateRequest, MessageType_Deprecated_PassphraseStateRequest);
+trezor_message_impl!(Deprecated_PassphraseStateAck, MessageType_Deprecated_PassphraseStateAck);
+trezor_message_impl!(Initialize, MessageType_Initialize, HostToDevice);
+trezor_message_impl!(GetFeatures, MessageType_GetFeatures, HostToDevice);
+trezor_message_impl!(Features, MessageType_Features, DeviceToHost);
+trezor_message_impl!(LockDevice, MessageType_LockDevice, HostToDevice);
+trezor_message_impl!(EndSession, MessageType_EndSession, HostToDevice);
+trezor_message_impl!(ApplySettings, MessageType_ApplySettings, HostToDevice);
+trezor_message_impl!(ApplyFlags, MessageType_ApplyFlags, HostToDevice);
+trezor_message_impl!(ChangePin, MessageType_ChangePin, HostToDevice);
+trezor_message_impl!(ChangeWipeCode, MessageType_ChangeWipeCode, HostToDevice);
+trezor_message_impl!(SdProtect, MessageType_SdProtect, HostToDevice);
+trezor_message_impl!(Ping, MessageType_Ping, HostToDevice);
+trezor_message_impl!(Cancel, MessageType_Cancel, HostToDevice);
+trezor_message_impl!(GetEntropy, MessageType_GetEntropy, HostToDevice);
+trezor_message_impl!(Entropy, MessageType_Entropy, DeviceToHost);
+trezor_message_impl!(WipeDevice, MessageType_WipeDevice, HostToDevice);
+trezor_message_impl!(LoadDevice, MessageType_LoadDevice, HostToDevice);
+trezor_message_impl!(ResetDevice, MessageType_ResetDevice, HostToDevice);
+trezor_message_impl!(BackupDevice, MessageType_BackupDevice, HostToDevice);
+trezor_message_impl!(EntropyRequest, MessageType_EntropyRequest, DeviceToHost);
+trezor_message_impl!(EntropyAck, MessageType_EntropyAck, HostToDevice);
+trezor_message_impl!(RecoveryDevice, MessageType_RecoveryDevice, HostToDevice);
+trezor_message_impl!(WordRequest, MessageType_WordRequest, DeviceToHost);
+trezor_message_impl!(WordAck, MessageType_WordAck, HostToDevice);
+trezor_message_impl!(SetU2FCounter, MessageType_SetU2FCounter, HostToDevice);
+trezor_message_impl!(GetNextU2FCounter, MessageType_GetNextU2FCounter, HostToDevice);
+trezor_message_impl!(NextU2FCounter, MessageType_NextU2FCounter, DeviceToHost);
+trezor_message_impl!(DoPreauthorized, MessageType_DoPreauthorized, HostToDevice);
+trezor_message_impl!(PreauthorizedRequest, MessageType_PreauthorizedRequest, DeviceToHost);
+trezor_message_impl!(CancelAuthorization, MessageType_CancelAuthorization, HostToDevice);
+trezor_message_impl!(RebootToBootloader, MessageType_RebootToBootloader, HostToDevice);
+trezor_message_impl!(GetPublicKey, MessageType_GetPublicKey, HostToDevice);
+trezor_message_impl!(PublicKey, MessageType_PublicKey, DeviceToHost);
+trezor_message_impl!(GetAddress, MessageType_GetAddress, HostToDevice);
+trezor_message_impl!(Address, MessageType_Address, DeviceToHost);
+trezor_message_impl!(GetOwnershipId, MessageType_GetOwnershipId, HostToDevice);
+trezor_message_impl!(OwnershipId, MessageType_OwnershipId, DeviceToHost);
+trezor_message_impl!(SignMessage, MessageType_SignMessage, HostToDevice);
+trezor_message_impl!(MessageSignature, MessageType_MessageSignature, DeviceToHost);
+trezor_message_impl!(VerifyMessage, MessageType_VerifyMessage, HostToDevice);
+trezor_message_impl!(SignTx, MessageType_SignTx, HostToDevice);
+trezor_message_impl!(TxRequest, MessageType_TxRequest, DeviceToHost);
+trezor_message_impl!(TxAck, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(TxAckInput, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(TxAckOutput, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(TxAckPrevMeta, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(TxAckPrevInput, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(TxAckPrevOutput, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(TxAckPrevExtraData, MessageType_TxAck, HostToDevice);
+trezor_message_impl!(GetOwnershipProof, MessageType_GetOwnershipProof, HostToDevice);
+trezor_message_impl!(OwnershipProof, MessageType_OwnershipProof, DeviceToHost);
+trezor_message_impl!(AuthorizeCoinJoin, MessageType_AuthorizeCoinJoin, HostToDevice);
+trezor_message_impl!(TezosGetAddress, MessageType_TezosGetAddress, HostToDevice);
+trezor_message_impl!(TezosAddress, MessageType_TezosAddress, DeviceToHost);
+trezor_message_impl!(TezosGetPublicKey, MessageType_TezosGetPublicKey, HostToDevice);
+trezor_message_impl!(TezosPublicKey, MessageType_TezosPublicKey, DeviceToHost);
+trezor_message_impl!(TezosSignTx, MessageType_TezosSignTx, HostToDevice);
+trezor_message_impl!(TezosSignedTx, MessageType_TezosSignedTx, DeviceToHost);
+trezor_message_impl!(DebugLinkDecision, MessageType_DebugLinkDecision, DebugOnly);
+trezor_message_impl!(DebugLinkLayout, MessageType_DebugLinkLayout, DebugOnly);
+trezor_message_impl!(DebugLinkReseedRandom, MessageType_DebugLinkReseedRandom, DebugOnly);
+trezor_message_impl!(DebugLinkRecordScreen, MessageType_DebugLinkRecordScreen, DebugOnly);
+trezor_message_impl!(DebugLinkGetState, MessageType_DebugLinkGetState, DebugOnly);
+trezor_message_impl!(DebugLinkState, MessageType_DebugLinkState, DebugOnly);
+trezor_message_impl!(DebugLinkStop, MessageType_DebugLinkStop, DebugOnly);
+trezor_message_impl!(DebugLinkLog, MessageType_DebugLinkLog, DebugOnly);
+trezor_message_impl!(DebugLinkWatchLayout, MessageType_DebugLinkWatchLayout, DebugOnly);
+
+any_trezor_message! {
+	Success => MessageType_Success,
+	Failure => MessageType_Failure,
+	ButtonRequest => MessageType_ButtonRequest,
+	ButtonAck => MessageType_ButtonAck,
+	PinMatrixRequest => MessageType_PinMatrixRequest,
+	PinMatrixAck => MessageType_PinMatrixAck,
+	PassphraseRequest => MessageType_PassphraseRequest,
+	PassphraseAck => MessageType_PassphraseAck,
+	Deprecated_PassphraseStateRequest => MessageType_Deprecated_PassphraseStateRequest,
+	Deprecated_PassphraseStateAck => MessageType_Deprecated_PassphraseStateAck,
+	Initialize => MessageType_Initialize,
+	GetFeatures => MessageType_GetFeatures,
+	Features => MessageType_Features,
+	LockDevice => MessageType_LockDevice,
+	EndSession => MessageType_EndSession,
+	ApplySettings => MessageType_ApplySettings,
+	ApplyFlags => MessageType_ApplyFlags,
+	ChangePin => MessageType_ChangePin,
+	ChangeWipeCode => MessageType_ChangeWipeCode,
+	SdProtect => MessageType_SdProtect,
+	Ping => MessageType_Ping,
+	Cancel => MessageType_Cancel,
+	GetEntropy => MessageType_GetEntropy,
+	Entropy => MessageType_Entropy,
+	WipeDevice => MessageType_WipeDevice,
+	LoadDevice => MessageType_LoadDevice,
+	ResetDevice => MessageType_ResetDevice,
+	BackupDevice => MessageType_BackupDevice,
+	EntropyRequest => MessageType_EntropyRequest,
+	EntropyAck => MessageType_EntropyAck,
+	RecoveryDevice => MessageType_RecoveryDevice,
+	WordRequest => MessageType_WordRequest,
+	WordAck => MessageType_WordAck,
+	SetU2FCounter => MessageType_SetU2FCounter,
+	GetNextU2FCounter => MessageType_GetNextU2FCounter,
+	NextU2FCounter => MessageType_NextU2FCounter,
+	DoPreauthorized => MessageType_DoPreauthorized,
+	PreauthorizedRequest => MessageType_PreauthorizedRequest,
+	CancelAuthorization => MessageType_CancelAuthorization,
+	RebootToBootloader => MessageType_RebootToBootloader,
+	GetPublicKey => MessageType_GetPublicKey,
+	PublicKey => MessageType_PublicKey,
+	GetAddress => MessageType_GetAddress,
+	Address => MessageType_Address,
+	GetOwnershipId => MessageType_GetOwnershipId,
+	OwnershipId => MessageType_OwnershipId,
+	SignMessage => MessageType_SignMessage,
+	MessageSignature => MessageType_MessageSignature,
+	VerifyMessage => MessageType_VerifyMessage,
+	SignTx => MessageType_SignTx,
+	TxRequest => MessageType_TxRequest,
+	TxAck => MessageType_TxAck,
+	GetOwnershipProof => MessageType_GetOwnershipProof,
+	OwnershipProof => MessageType_OwnershipProof,
+	AuthorizeCoinJoin => MessageType_AuthorizeCoinJoin,
+	TezosGetAddress => MessageType_TezosGetAddress,
+	TezosAddress => MessageType_TezosAddress,
+	TezosGetPublicKey => MessageType_TezosGetPublicKey,
+	TezosPublicKey => MessageType_TezosPublicKey,
+	TezosSignTx => MessageType_TezosSignTx,
+	TezosSignedTx => MessageType_TezosSignedTx,
+	DebugLinkDecision => MessageType_DebugLinkDecision,
+	DebugLinkLayout => MessageType_DebugLinkLayout,
+	DebugLinkReseedRandom => MessageType_DebugLinkReseedRandom,
+	DebugLinkRecordScreen => MessageType_DebugLinkRecordScreen,
+	DebugLinkGetState => MessageType_DebugLinkGetState,
+	DebugLinkState => MessageType_DebugLinkState,
+	DebugLinkStop => MessageType_DebugLinkStop,
+	DebugLinkLog => MessageType_DebugLinkLog,
+	DebugLinkWatchLayout => MessageType_DebugLinkWatchLayout,
+}
+
+bitcoin_only_message_types! {
+	MessageType_Initialize,
+	MessageType_Ping,
+	MessageType_Success,
+	MessageType_Failure,
+	MessageType_ChangePin,
+	MessageType_WipeDevice,
+	MessageType_FirmwareErase,
+	MessageType_FirmwareUpload,
+	MessageType_FirmwareRequest,
+	MessageType_GetEntropy,
+	MessageType_Entropy,
+	MessageType_GetPublicKey,
+	MessageType_PublicKey,
+	MessageType_LoadDevice,
+	MessageType_ResetDevice,
+	MessageType_SignTx,
+	MessageType_Features,
+	MessageType_PinMatrixRequest,
+	MessageType_PinMatrixAck,
+	MessageType_Cancel,
+	MessageType_TxRequest,
+	MessageType_TxAck,
+	MessageType_CipherKeyValue,
+	MessageType_LockDevice,
+	MessageType_ApplySettings,
+	MessageType_ButtonRequest,
+	MessageType_ButtonAck,
+	MessageType_ApplyFlags,
+	MessageType_GetAddress,
+	MessageType_Address,
+	MessageType_SelfTest,
+	MessageType_BackupDevice,
+	MessageType_EntropyRequest,
+	MessageType_EntropyAck,
+	MessageType_SignMessage,
+	MessageType_VerifyMessage,
+	MessageType_MessageSignature,
+	MessageType_PassphraseRequest,
+	MessageType_PassphraseAck,
+	MessageType_GetOwnershipId,
+	MessageType_OwnershipId,
+	MessageType_RecoveryDevice,
+	MessageType_WordRequest,
+	MessageType_WordAck,
+	MessageType_CipheredKeyValue,
+	MessageType_GetOwnershipProof,
+	MessageType_OwnershipProof,
+	MessageType_AuthorizeCoinJoin,
+	MessageType_SignIdentity,
+	MessageType_SignedIdentity,
+	MessageType_GetFeatures,
+	MessageType_GetECDHSessionKey,
+	MessageType_ECDHSessionKey,
+	MessageType_CosiCommit,
+	MessageType_CosiCommitment,
+	MessageType_CosiSign,
+	MessageType_CosiSignature,
+	MessageType_SdProtect,
+	MessageType_ChangeWipeCode,
+	MessageType_EndSession,
+	MessageType_DoPreauthorized,
+	MessageType_PreauthorizedRequest,
+	MessageType_CancelAuthorization,
+	MessageType_RebootToBootloader,
+	MessageType_DebugLinkDecision,
+	MessageType_DebugLinkGetState,
+	MessageType_DebugLinkState,
+	MessageType_DebugLinkStop,
+	MessageType_DebugLinkLog,
+	MessageType_DebugLinkMemoryRead,
+	MessageType_DebugLinkMemory,
+	MessageType_DebugLinkMemoryWrite,
+	MessageType_DebugLinkFlashErase,
+	MessageType_DebugLinkLayout,
+	MessageType_DebugLinkReseedRandom,
+	MessageType_DebugLinkRecordScreen,
+	MessageType_DebugLinkEraseSdCard,
+	MessageType_DebugLinkWatchLayout,
+}
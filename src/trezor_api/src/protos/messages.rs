@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.25.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -21,9 +21,10 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_25_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum MessageType {
     MessageType_Initialize = 0,
     MessageType_Ping = 1,
@@ -727,7 +728,7 @@ pub mod exts {
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x0emessages.proto\x12\x12hw.trezor.messages\x1a\x20google/protobuf/de\
     scriptor.proto*\xdbG\n\x0bMessageType\x12(\n\x16MessageType_Initialize\
-    \x10\0\x1a\x0c\x80\xa6\x1d\x01\xb0\xb5\x18\x01\x90\xb5\x18\x01\x12\x1e\n\
+    \x10\0\x1a\x0c\xb0\xb5\x18\x01\x80\xa6\x1d\x01\x90\xb5\x18\x01\x12\x1e\n\
     \x10MessageType_Ping\x10\x01\x1a\x08\x80\xa6\x1d\x01\x90\xb5\x18\x01\x12\
     !\n\x13MessageType_Success\x10\x02\x1a\x08\x80\xa6\x1d\x01\x98\xb5\x18\
     \x01\x12!\n\x13MessageType_Failure\x10\x03\x1a\x08\x80\xa6\x1d\x01\x98\
@@ -742,7 +743,7 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x12*\n\x1cMessageType_PinMatrixRequest\x10\x12\x1a\x08\x80\xa6\x1d\x01\
     \x98\xb5\x18\x01\x12.\n\x18MessageType_PinMatrixAck\x10\x13\x1a\x10\xc0\
     \xb5\x18\x01\xb0\xb5\x18\x01\x80\xa6\x1d\x01\x90\xb5\x18\x01\x12$\n\x12M\
-    essageType_Cancel\x10\x14\x1a\x0c\x80\xa6\x1d\x01\xb0\xb5\x18\x01\x90\
+    essageType_Cancel\x10\x14\x1a\x0c\xb0\xb5\x18\x01\x80\xa6\x1d\x01\x90\
     \xb5\x18\x01\x12$\n\x16MessageType_LockDevice\x10\x18\x1a\x08\x80\xa6\
     \x1d\x01\x90\xb5\x18\x01\x12'\n\x19MessageType_ApplySettings\x10\x19\x1a\
     \x08\x80\xa6\x1d\x01\x90\xb5\x18\x01\x12'\n\x19MessageType_ButtonRequest\
@@ -809,8 +810,8 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x01\x90\xb5\x18\x01\x12'\n\x19MessageType_CosiSignature\x10J\x1a\x08\
     \x80\xa6\x1d\x01\x98\xb5\x18\x01\x123\n\x1dMessageType_DebugLinkDecision\
     \x10d\x1a\x10\xc0\xb5\x18\x01\xb0\xb5\x18\x01\x80\xa6\x1d\x01\xa0\xb5\
-    \x18\x01\x12/\n\x1dMessageType_DebugLinkGetState\x10e\x1a\x0c\x80\xa6\
-    \x1d\x01\xb0\xb5\x18\x01\xa0\xb5\x18\x01\x12(\n\x1aMessageType_DebugLink\
+    \x18\x01\x12/\n\x1dMessageType_DebugLinkGetState\x10e\x1a\x0c\xb0\xb5\
+    \x18\x01\x80\xa6\x1d\x01\xa0\xb5\x18\x01\x12(\n\x1aMessageType_DebugLink\
     State\x10f\x1a\x08\x80\xa6\x1d\x01\xa8\xb5\x18\x01\x12'\n\x19MessageType\
     _DebugLinkStop\x10g\x1a\x08\x80\xa6\x1d\x01\xa0\xb5\x18\x01\x12&\n\x18Me\
     ssageType_DebugLinkLog\x10h\x1a\x08\x80\xa6\x1d\x01\xa8\xb5\x18\x01\x12-\
@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.25.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -21,9 +21,10 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_25_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct MultisigRedeemScriptType {
     // message fields
     pub pubkeys: ::protobuf::RepeatedField<MultisigRedeemScriptType_HDNodePathType>,
@@ -32,7 +33,9 @@ pub struct MultisigRedeemScriptType {
     pub nodes: ::protobuf::RepeatedField<super::messages_common::HDNodeType>,
     pub address_n: ::std::vec::Vec<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -363,12 +366,15 @@ impl ::protobuf::reflect::ProtobufValue for MultisigRedeemScriptType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct MultisigRedeemScriptType_HDNodePathType {
     // message fields
     pub node: ::protobuf::SingularPtrField<super::messages_common::HDNodeType>,
     pub address_n: ::std::vec::Vec<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -581,6 +587,7 @@ impl ::protobuf::reflect::ProtobufValue for MultisigRedeemScriptType_HDNodePathT
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GetPublicKey {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -590,7 +597,9 @@ pub struct GetPublicKey {
     script_type: ::std::option::Option<InputScriptType>,
     ignore_xpub_magic: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -956,13 +965,16 @@ impl ::protobuf::reflect::ProtobufValue for GetPublicKey {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PublicKey {
     // message fields
     pub node: ::protobuf::SingularPtrField<super::messages_common::HDNodeType>,
     xpub: ::protobuf::SingularField<::std::string::String>,
     root_fingerprint: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1227,6 +1239,7 @@ impl ::protobuf::reflect::ProtobufValue for PublicKey {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GetAddress {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -1236,7 +1249,9 @@ pub struct GetAddress {
     script_type: ::std::option::Option<InputScriptType>,
     ignore_xpub_magic: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1607,11 +1622,14 @@ impl ::protobuf::reflect::ProtobufValue for GetAddress {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Address {
     // message fields
     address: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1779,6 +1797,7 @@ impl ::protobuf::reflect::ProtobufValue for Address {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GetOwnershipId {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -1786,7 +1805,9 @@ pub struct GetOwnershipId {
     pub multisig: ::protobuf::SingularPtrField<MultisigRedeemScriptType>,
     script_type: ::std::option::Option<InputScriptType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2081,11 +2102,14 @@ impl ::protobuf::reflect::ProtobufValue for GetOwnershipId {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct OwnershipId {
     // message fields
     ownership_id: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2253,6 +2277,7 @@ impl ::protobuf::reflect::ProtobufValue for OwnershipId {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct SignMessage {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -2260,7 +2285,9 @@ pub struct SignMessage {
     coin_name: ::protobuf::SingularField<::std::string::String>,
     script_type: ::std::option::Option<InputScriptType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2553,12 +2580,15 @@ impl ::protobuf::reflect::ProtobufValue for SignMessage {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct MessageSignature {
     // message fields
     address: ::protobuf::SingularField<::std::string::String>,
     signature: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2780,6 +2810,7 @@ impl ::protobuf::reflect::ProtobufValue for MessageSignature {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct VerifyMessage {
     // message fields
     address: ::protobuf::SingularField<::std::string::String>,
@@ -2787,7 +2818,9 @@ pub struct VerifyMessage {
     message: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     coin_name: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -3114,6 +3147,7 @@ impl ::protobuf::reflect::ProtobufValue for VerifyMessage {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct SignTx {
     // message fields
     outputs_count: ::std::option::Option<u32>,
@@ -3129,7 +3163,9 @@ pub struct SignTx {
     amount_unit: ::std::option::Option<AmountUnit>,
     decred_staking_ticket: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -3714,13 +3750,16 @@ impl ::protobuf::reflect::ProtobufValue for SignTx {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxRequest {
     // message fields
     request_type: ::std::option::Option<TxRequest_RequestType>,
     pub details: ::protobuf::SingularPtrField<TxRequest_TxRequestDetailsType>,
     pub serialized: ::protobuf::SingularPtrField<TxRequest_TxRequestSerializedType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -3980,6 +4019,7 @@ impl ::protobuf::reflect::ProtobufValue for TxRequest {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxRequest_TxRequestDetailsType {
     // message fields
     request_index: ::std::option::Option<u32>,
@@ -3987,7 +4027,9 @@ pub struct TxRequest_TxRequestDetailsType {
     extra_data_len: ::std::option::Option<u32>,
     extra_data_offset: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -4266,13 +4308,16 @@ impl ::protobuf::reflect::ProtobufValue for TxRequest_TxRequestDetailsType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxRequest_TxRequestSerializedType {
     // message fields
     signature_index: ::std::option::Option<u32>,
     signature: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     serialized_tx: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -4526,6 +4571,7 @@ impl ::protobuf::reflect::ProtobufValue for TxRequest_TxRequestSerializedType {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum TxRequest_RequestType {
     TXINPUT = 0,
     TXOUTPUT = 1,
@@ -4591,11 +4637,14 @@ impl ::protobuf::reflect::ProtobufValue for TxRequest_RequestType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAck {
     // message fields
     pub tx: ::protobuf::SingularPtrField<TxAck_TransactionType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -4765,6 +4814,7 @@ impl ::protobuf::reflect::ProtobufValue for TxAck {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAck_TransactionType {
     // message fields
     version: ::std::option::Option<u32>,
@@ -4782,7 +4832,9 @@ pub struct TxAck_TransactionType {
     timestamp: ::std::option::Option<u32>,
     branch_id: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -5471,6 +5523,7 @@ impl ::protobuf::reflect::ProtobufValue for TxAck_TransactionType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAck_TransactionType_TxInputType {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -5489,7 +5542,9 @@ pub struct TxAck_TransactionType_TxInputType {
     orig_index: ::std::option::Option<u32>,
     decred_staking_spend: ::std::option::Option<DecredStakingSpendType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -6269,13 +6324,16 @@ impl ::protobuf::reflect::ProtobufValue for TxAck_TransactionType_TxInputType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAck_TransactionType_TxOutputBinType {
     // message fields
     amount: ::std::option::Option<u64>,
     script_pubkey: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     decred_script_version: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -6522,6 +6580,7 @@ impl ::protobuf::reflect::ProtobufValue for TxAck_TransactionType_TxOutputBinTyp
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAck_TransactionType_TxOutputType {
     // message fields
     address: ::protobuf::SingularField<::std::string::String>,
@@ -6533,7 +6592,9 @@ pub struct TxAck_TransactionType_TxOutputType {
     orig_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     orig_index: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -7009,6 +7070,7 @@ impl ::protobuf::reflect::ProtobufValue for TxAck_TransactionType_TxOutputType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxInput {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -7027,7 +7089,9 @@ pub struct TxInput {
     orig_index: ::std::option::Option<u32>,
     decred_staking_spend: ::std::option::Option<DecredStakingSpendType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -7810,6 +7874,7 @@ impl ::protobuf::reflect::ProtobufValue for TxInput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxOutput {
     // message fields
     address: ::protobuf::SingularField<::std::string::String>,
@@ -7821,7 +7886,9 @@ pub struct TxOutput {
     orig_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     orig_index: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -8297,6 +8364,7 @@ impl ::protobuf::reflect::ProtobufValue for TxOutput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PrevTx {
     // message fields
     version: ::std::option::Option<u32>,
@@ -8309,7 +8377,9 @@ pub struct PrevTx {
     timestamp: ::std::option::Option<u32>,
     branch_id: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -8777,6 +8847,7 @@ impl ::protobuf::reflect::ProtobufValue for PrevTx {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PrevInput {
     // message fields
     prev_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -8785,7 +8856,9 @@ pub struct PrevInput {
     sequence: ::std::option::Option<u32>,
     decred_tree: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -9127,13 +9200,16 @@ impl ::protobuf::reflect::ProtobufValue for PrevInput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PrevOutput {
     // message fields
     amount: ::std::option::Option<u64>,
     script_pubkey: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     decred_script_version: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -9380,11 +9456,14 @@ impl ::protobuf::reflect::ProtobufValue for PrevOutput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckInput {
     // message fields
     pub tx: ::protobuf::SingularPtrField<TxAckInput_TxAckInputWrapper>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -9557,11 +9636,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckInput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckInput_TxAckInputWrapper {
     // message fields
     pub input: ::protobuf::SingularPtrField<TxInput>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -9734,11 +9816,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckInput_TxAckInputWrapper {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckOutput {
     // message fields
     pub tx: ::protobuf::SingularPtrField<TxAckOutput_TxAckOutputWrapper>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -9911,11 +9996,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckOutput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckOutput_TxAckOutputWrapper {
     // message fields
     pub output: ::protobuf::SingularPtrField<TxOutput>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -10088,11 +10176,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckOutput_TxAckOutputWrapper {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevMeta {
     // message fields
     pub tx: ::protobuf::SingularPtrField<PrevTx>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -10265,11 +10356,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevMeta {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevInput {
     // message fields
     pub tx: ::protobuf::SingularPtrField<TxAckPrevInput_TxAckPrevInputWrapper>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -10442,11 +10536,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevInput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevInput_TxAckPrevInputWrapper {
     // message fields
     pub input: ::protobuf::SingularPtrField<PrevInput>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -10619,11 +10716,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevInput_TxAckPrevInputWrapper
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevOutput {
     // message fields
     pub tx: ::protobuf::SingularPtrField<TxAckPrevOutput_TxAckPrevOutputWrapper>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -10796,11 +10896,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevOutput {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevOutput_TxAckPrevOutputWrapper {
     // message fields
     pub output: ::protobuf::SingularPtrField<PrevOutput>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -10973,11 +11076,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevOutput_TxAckPrevOutputWrapp
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevExtraData {
     // message fields
     pub tx: ::protobuf::SingularPtrField<TxAckPrevExtraData_TxAckPrevExtraDataWrapper>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -11150,11 +11256,14 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevExtraData {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TxAckPrevExtraData_TxAckPrevExtraDataWrapper {
     // message fields
     extra_data_chunk: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -11322,6 +11431,7 @@ impl ::protobuf::reflect::ProtobufValue for TxAckPrevExtraData_TxAckPrevExtraDat
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct GetOwnershipProof {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -11332,7 +11442,9 @@ pub struct GetOwnershipProof {
     pub ownership_ids: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
     commitment_data: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -11756,12 +11868,15 @@ impl ::protobuf::reflect::ProtobufValue for GetOwnershipProof {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct OwnershipProof {
     // message fields
     ownership_proof: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     signature: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -11983,6 +12098,7 @@ impl ::protobuf::reflect::ProtobufValue for OwnershipProof {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct AuthorizeCoinJoin {
     // message fields
     coordinator: ::protobuf::SingularField<::std::string::String>,
@@ -11993,7 +12109,9 @@ pub struct AuthorizeCoinJoin {
     script_type: ::std::option::Option<InputScriptType>,
     amount_unit: ::std::option::Option<AmountUnit>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -12399,6 +12517,7 @@ impl ::protobuf::reflect::ProtobufValue for AuthorizeCoinJoin {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum InputScriptType {
     SPENDADDRESS = 0,
     SPENDMULTISIG = 1,
@@ -12458,6 +12577,7 @@ impl ::protobuf::reflect::ProtobufValue for InputScriptType {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum OutputScriptType {
     PAYTOADDRESS = 0,
     PAYTOSCRIPTHASH = 1,
@@ -12520,6 +12640,7 @@ impl ::protobuf::reflect::ProtobufValue for OutputScriptType {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum DecredStakingSpendType {
     SSGen = 0,
     SSRTX = 1,
@@ -12570,6 +12691,7 @@ impl ::protobuf::reflect::ProtobufValue for DecredStakingSpendType {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum AmountUnit {
     BITCOIN = 0,
     MILLIBITCOIN = 1,
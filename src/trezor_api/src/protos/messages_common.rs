@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.25.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -21,14 +21,17 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_25_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Success {
     // message fields
     message: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -193,12 +196,15 @@ impl ::protobuf::reflect::ProtobufValue for Success {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Failure {
     // message fields
     code: ::std::option::Option<Failure_FailureType>,
     message: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -397,6 +403,7 @@ impl ::protobuf::reflect::ProtobufValue for Failure {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Failure_FailureType {
     Failure_UnexpectedMessage = 1,
     Failure_ButtonExpected = 2,
@@ -487,12 +494,15 @@ impl ::protobuf::reflect::ProtobufValue for Failure_FailureType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct ButtonRequest {
     // message fields
     code: ::std::option::Option<ButtonRequest_ButtonRequestType>,
     pages: ::std::option::Option<u32>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -678,6 +688,7 @@ impl ::protobuf::reflect::ProtobufValue for ButtonRequest {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ButtonRequest_ButtonRequestType {
     ButtonRequest_Other = 1,
     ButtonRequest_FeeOverThreshold = 2,
@@ -783,9 +794,12 @@ impl ::protobuf::reflect::ProtobufValue for ButtonRequest_ButtonRequestType {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct ButtonAck {
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -899,11 +913,14 @@ impl ::protobuf::reflect::ProtobufValue for ButtonAck {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PinMatrixRequest {
     // message fields
     field_type: ::std::option::Option<PinMatrixRequest_PinMatrixRequestType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1051,6 +1068,7 @@ impl ::protobuf::reflect::ProtobufValue for PinMatrixRequest {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum PinMatrixRequest_PinMatrixRequestType {
     PinMatrixRequestType_Current = 1,
     PinMatrixRequestType_NewFirst = 2,
@@ -1111,11 +1129,14 @@ impl ::protobuf::reflect::ProtobufValue for PinMatrixRequest_PinMatrixRequestTyp
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PinMatrixAck {
     // message fields
     pin: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1283,11 +1304,14 @@ impl ::protobuf::reflect::ProtobufValue for PinMatrixAck {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PassphraseRequest {
     // message fields
     _on_device: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1439,13 +1463,16 @@ impl ::protobuf::reflect::ProtobufValue for PassphraseRequest {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct PassphraseAck {
     // message fields
     passphrase: ::protobuf::SingularField<::std::string::String>,
     _state: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     on_device: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1699,11 +1726,14 @@ impl ::protobuf::reflect::ProtobufValue for PassphraseAck {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Deprecated_PassphraseStateRequest {
     // message fields
     state: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1868,9 +1898,12 @@ impl ::protobuf::reflect::ProtobufValue for Deprecated_PassphraseStateRequest {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Deprecated_PassphraseStateAck {
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1984,6 +2017,7 @@ impl ::protobuf::reflect::ProtobufValue for Deprecated_PassphraseStateAck {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct HDNodeType {
     // message fields
     depth: ::std::option::Option<u32>,
@@ -1993,7 +2027,9 @@ pub struct HDNodeType {
     private_key: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     public_key: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
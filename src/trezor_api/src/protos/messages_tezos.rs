@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.25.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -21,15 +21,18 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_25_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosGetAddress {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
     show_display: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -221,11 +224,14 @@ impl ::protobuf::reflect::ProtobufValue for TezosGetAddress {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosAddress {
     // message fields
     address: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -390,12 +396,15 @@ impl ::protobuf::reflect::ProtobufValue for TezosAddress {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosGetPublicKey {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
     show_display: ::std::option::Option<bool>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -587,11 +596,14 @@ impl ::protobuf::reflect::ProtobufValue for TezosGetPublicKey {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosPublicKey {
     // message fields
     public_key: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -756,6 +768,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosPublicKey {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx {
     // message fields
     pub address_n: ::std::vec::Vec<u32>,
@@ -767,7 +780,9 @@ pub struct TezosSignTx {
     pub proposal: ::protobuf::SingularPtrField<TezosSignTx_TezosProposalOp>,
     pub ballot: ::protobuf::SingularPtrField<TezosSignTx_TezosBallotOp>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1308,12 +1323,15 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosContractID {
     // message fields
     tag: ::std::option::Option<TezosSignTx_TezosContractID_TezosContractType>,
     hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1512,6 +1530,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosContractID {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum TezosSignTx_TezosContractID_TezosContractType {
     Implicit = 0,
     Originated = 1,
@@ -1562,6 +1581,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosContractID_TezosCon
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosRevealOp {
     // message fields
     source: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -1571,7 +1591,9 @@ pub struct TezosSignTx_TezosRevealOp {
     storage_limit: ::std::option::Option<u64>,
     public_key: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1939,6 +1961,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosRevealOp {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosTransactionOp {
     // message fields
     source: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -1951,7 +1974,9 @@ pub struct TezosSignTx_TezosTransactionOp {
     parameters: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     pub parameters_manager: ::protobuf::SingularPtrField<TezosSignTx_TezosTransactionOp_TezosParametersManager>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2469,13 +2494,16 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosTransactionOp {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosTransactionOp_TezosParametersManager {
     // message fields
     set_delegate: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     cancel_delegate: ::std::option::Option<bool>,
     pub transfer: ::protobuf::SingularPtrField<TezosSignTx_TezosTransactionOp_TezosParametersManager_TezosManagerTransfer>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2734,12 +2762,15 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosTransactionOp_Tezos
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosTransactionOp_TezosParametersManager_TezosManagerTransfer {
     // message fields
     pub destination: ::protobuf::SingularPtrField<TezosSignTx_TezosContractID>,
     amount: ::std::option::Option<u64>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -2947,6 +2978,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosTransactionOp_Tezos
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosOriginationOp {
     // message fields
     source: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -2961,7 +2993,9 @@ pub struct TezosSignTx_TezosOriginationOp {
     delegate: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     script: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -3545,6 +3579,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosOriginationOp {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosDelegationOp {
     // message fields
     source: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -3554,7 +3589,9 @@ pub struct TezosSignTx_TezosDelegationOp {
     storage_limit: ::std::option::Option<u64>,
     delegate: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -3922,13 +3959,16 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosDelegationOp {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosProposalOp {
     // message fields
     source: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     period: ::std::option::Option<u64>,
     pub proposals: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -4171,6 +4211,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosProposalOp {
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignTx_TezosBallotOp {
     // message fields
     source: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -4178,7 +4219,9 @@ pub struct TezosSignTx_TezosBallotOp {
     proposal: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     ballot: ::std::option::Option<TezosSignTx_TezosBallotOp_TezosBallotType>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -4466,6 +4509,7 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosBallotOp {
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum TezosSignTx_TezosBallotOp_TezosBallotType {
     Yay = 0,
     Nay = 1,
@@ -4519,13 +4563,16 @@ impl ::protobuf::reflect::ProtobufValue for TezosSignTx_TezosBallotOp_TezosBallo
 }
 
 #[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TezosSignedTx {
     // message fields
     signature: ::protobuf::SingularField<::std::string::String>,
     sig_op_contents: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     operation_hash: ::protobuf::SingularField<::std::string::String>,
     // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
     pub cached_size: ::protobuf::CachedSize,
 }
 
@@ -1,26 +1,45 @@
 use std::str::FromStr;
 use std::fmt;
 
+// The `pure-codegen` feature generates the messages into `OUT_DIR`, see `build.rs`.
+#[cfg(feature = "pure-codegen")]
+include!(concat!(env!("OUT_DIR"), "/protos/mod.rs"));
+
+#[cfg(not(feature = "pure-codegen"))]
 pub mod messages_common;
 
 pub use messages_common::*;
 
+#[cfg(not(feature = "pure-codegen"))]
 pub mod messages;
 
 pub use messages::*;
 
+#[cfg(not(feature = "pure-codegen"))]
 pub mod messages_tezos;
 
 pub use messages_tezos::*;
 
+#[cfg(not(feature = "pure-codegen"))]
 pub mod messages_management;
 
 pub use messages_management::*;
 
+#[cfg(not(feature = "pure-codegen"))]
 pub mod messages_bitcoin;
 
 pub use messages_bitcoin::*;
 
+#[cfg(not(feature = "pure-codegen"))]
+pub mod messages_debug;
+
+// Not a glob, since every module has its own `file_descriptor_proto`.
+pub use messages_debug::{
+    DebugLinkDecision, DebugLinkDecision_DebugSwipeDirection, DebugLinkGetState, DebugLinkLayout,
+    DebugLinkLog, DebugLinkRecordScreen, DebugLinkReseedRandom, DebugLinkState, DebugLinkStop,
+    DebugLinkWatchLayout,
+};
+
 pub const HARDENED_PATH: u32 = 2147483648;
 
 #[derive(PartialEq, Debug, Clone)]
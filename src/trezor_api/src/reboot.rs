@@ -0,0 +1,279 @@
+//! # Reboot into the bootloader
+//!
+//! After [Trezor::reboot_to_bootloader] the device leaves the bus and comes back in
+//! bootloader mode, [wait_for_bootloader] finds it again among the devices plugged in.
+//!
+//! [Trezor::reboot_to_bootloader]: crate::Trezor::reboot_to_bootloader
+
+use std::time::Duration;
+
+use crate::{
+    client, find_devices, time, transport, AvailableDevice, DeviceMode, Error, Result, TrezorModel,
+};
+
+/// How often the devices are enumerated while waiting for a reboot.
+const REBOOT_POLL_INTERVAL_MS: u64 = 500;
+/// How many times the devices are enumerated before giving up on a reboot.
+const REBOOT_POLL_ATTEMPTS: usize = 60;
+
+/// What tells a connected device apart from the others once it rebooted.
+#[derive(Clone, Debug)]
+pub(crate) struct DeviceIdentity {
+    model: TrezorModel,
+    /// See [AvailableDevice::id].
+    id: String,
+    /// See [AvailableDevice::has_stable_id].
+    stable_id: bool,
+    /// Whether [find_devices] lists the device, only USB devices are.
+    usb: bool,
+}
+
+impl DeviceIdentity {
+    pub(crate) fn of(device: &AvailableDevice) -> DeviceIdentity {
+        DeviceIdentity {
+            model: device.model,
+            id: device.id(),
+            stable_id: device.has_stable_id(),
+            usb: device.transport.is_usb(),
+        }
+    }
+
+    /// Fails with [Error::UnsupportedReboot] if the device can't be found again
+    /// after a reboot.
+    pub(crate) fn check_reboot(&self) -> Result<()> {
+        match self.usb {
+            true => Ok(()),
+            false => Err(Error::UnsupportedReboot),
+        }
+    }
+}
+
+/// What the enumerated devices tell about the rebooted one.
+#[derive(Debug)]
+enum Rebooted {
+    /// The device is back in bootloader mode.
+    Found(AvailableDevice),
+    /// The USB ids don't tell whether the device runs the bootloader.
+    Ambiguous(AvailableDevice),
+    /// The device didn't come back yet.
+    Pending,
+}
+
+/// Pick the rebooted device out of the enumerated ones.
+///
+/// A device with a stable id is recognized by it. Otherwise it is the one whose
+/// id was not there before the reboot, since its position on the bus changed.
+fn pick_rebooted(
+    target: &DeviceIdentity,
+    before: &[String],
+    devices: Vec<AvailableDevice>,
+) -> Rebooted {
+    let mut candidates = devices.into_iter().filter(|device| {
+        !device.debug
+            && match target.stable_id {
+                true => device.id() == target.id,
+                false => !device.has_stable_id() && !before.contains(&device.id()),
+            }
+    });
+    let mut ambiguous = Vec::new();
+    for device in &mut candidates {
+        match (device.mode, device.model) {
+            (DeviceMode::Bootloader, _) => return Rebooted::Found(device),
+            // The older bootloaders of Trezor Model One keep the ids of the firmware.
+            (DeviceMode::Normal, TrezorModel::One) if target.model == TrezorModel::One => {
+                ambiguous.push(device)
+            }
+            (DeviceMode::Normal, _) => {}
+        }
+    }
+    // Only a single candidate is asked, to leave the devices plugged in meanwhile alone.
+    match ambiguous.len() {
+        1 => Rebooted::Ambiguous(ambiguous.remove(0)),
+        _ => Rebooted::Pending,
+    }
+}
+
+/// Wait for the device to re-enumerate in bootloader mode.
+///
+/// Used after [Trezor::reboot_to_bootloader]. `device` is the device before the
+/// reboot and `before` are the ids of all the devices found before it, see
+/// [AvailableDevice::id]. A device with a stable id keeps it across the reboot,
+/// the others come back with an id that was not there before.
+///
+/// The USB ids tell the bootloaders apart, except the older ones of Trezor Model One.
+/// Such a device is connected to and checked through its `Features`. Fails with
+/// [Error::UnsupportedReboot] for the devices [find_devices] doesn't list, and
+/// with [Error::NoDeviceFound] if the device doesn't show up in time.
+///
+/// [Trezor::reboot_to_bootloader]: crate::Trezor::reboot_to_bootloader
+pub async fn wait_for_bootloader(
+    device: &AvailableDevice,
+    before: &[String],
+) -> Result<AvailableDevice> {
+    wait_for_identity(&DeviceIdentity::of(device), before).await
+}
+
+pub(crate) async fn wait_for_identity(
+    target: &DeviceIdentity,
+    before: &[String],
+) -> Result<AvailableDevice> {
+    target.check_reboot()?;
+    for _ in 0..REBOOT_POLL_ATTEMPTS {
+        time::sleep(Duration::from_millis(REBOOT_POLL_INTERVAL_MS)).await;
+
+        // The device may be half-enumerated while rebooting, so enumeration errors are not fatal.
+        let devices = match find_devices().await {
+            Ok(devices) => devices,
+            Err(_) => continue,
+        };
+        let mut device = match pick_rebooted(target, before, devices) {
+            Rebooted::Found(device) => device,
+            Rebooted::Ambiguous(device) => match is_bootloader(&device).await {
+                Ok(true) => device,
+                Ok(false) => continue,
+                Err(e) => {
+                    log::debug!("skipping {}: {}", device, e);
+                    continue;
+                }
+            },
+            Rebooted::Pending => continue,
+        };
+        // The newer bootloaders of Trezor Model One share the ids of Trezor Model T.
+        device.model = target.model;
+        device.mode = DeviceMode::Bootloader;
+        return Ok(device);
+    }
+    Err(Error::NoDeviceFound)
+}
+
+/// Connect to the device and check whether it runs the bootloader.
+async fn is_bootloader(device: &AvailableDevice) -> Result<bool> {
+    let transport = transport::connect(device).map_err(Error::TransportConnect)?;
+    let mut trezor = client::trezor_with_transport(device.model, transport);
+    let init = trezor.init_device().await;
+    let bootloader_mode = trezor.features().map(|f| f.get_bootloader_mode());
+    trezor.close().await?;
+    init?;
+    Ok(bootloader_mode.unwrap_or(false))
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::transport::usb::AvailableUsbTransport;
+    use crate::transport::AvailableDeviceTransport;
+
+    fn usb_device(
+        model: TrezorModel,
+        mode: DeviceMode,
+        address: u8,
+        serial: &str,
+    ) -> AvailableDevice {
+        AvailableDevice {
+            model,
+            mode,
+            debug: false,
+            transport: AvailableDeviceTransport::Usb(AvailableUsbTransport {
+                bus: 1,
+                address,
+                serial_number: Some(serial.to_owned()),
+            }),
+        }
+    }
+
+    const NO_SERIAL: &str = "000000000000000000000000";
+
+    #[test]
+    fn test_stable_id() {
+        // Trezor Model One keeps its serial number in the bootloader.
+        let one = usb_device(TrezorModel::One, DeviceMode::Normal, 4, "2E8B9D0F11C34A87");
+        let target = DeviceIdentity::of(&one);
+        let before = vec![one.id()];
+
+        // Its newer bootloaders share the ids of the Trezor Model T bootloader.
+        let devices = vec![
+            usb_device(TrezorModel::T, DeviceMode::Bootloader, 5, NO_SERIAL),
+            usb_device(
+                TrezorModel::T,
+                DeviceMode::Bootloader,
+                6,
+                "2E8B9D0F11C34A87",
+            ),
+        ];
+        match pick_rebooted(&target, &before, devices) {
+            Rebooted::Found(device) => assert_eq!(device.id(), one.id()),
+            res => panic!("unexpected {:?}", res),
+        }
+
+        // The older ones keep the ids of the firmware.
+        let devices = vec![usb_device(
+            TrezorModel::One,
+            DeviceMode::Normal,
+            6,
+            "2E8B9D0F11C34A87",
+        )];
+        assert!(matches!(
+            pick_rebooted(&target, &before, devices),
+            Rebooted::Ambiguous(_)
+        ));
+
+        let devices = vec![usb_device(
+            TrezorModel::One,
+            DeviceMode::Normal,
+            7,
+            "9D0F11C34A872E8B",
+        )];
+        assert!(matches!(
+            pick_rebooted(&target, &before, devices),
+            Rebooted::Pending
+        ));
+    }
+
+    #[test]
+    fn test_unstable_id() {
+        let t = usb_device(TrezorModel::T, DeviceMode::Normal, 4, NO_SERIAL);
+        let other = usb_device(TrezorModel::T, DeviceMode::Bootloader, 5, NO_SERIAL);
+        let target = DeviceIdentity::of(&t);
+        let before = vec![t.id(), other.id()];
+
+        // The device is still rebooting, the other one was in the bootloader already.
+        let devices = vec![usb_device(
+            TrezorModel::T,
+            DeviceMode::Bootloader,
+            5,
+            NO_SERIAL,
+        )];
+        assert!(matches!(
+            pick_rebooted(&target, &before, devices),
+            Rebooted::Pending
+        ));
+
+        // A Trezor Model T plugged in meanwhile.
+        let devices = vec![
+            usb_device(TrezorModel::T, DeviceMode::Bootloader, 5, NO_SERIAL),
+            usb_device(TrezorModel::T, DeviceMode::Normal, 7, NO_SERIAL),
+            usb_device(TrezorModel::T, DeviceMode::Bootloader, 8, NO_SERIAL),
+        ];
+        match pick_rebooted(&target, &before, devices) {
+            Rebooted::Found(device) => assert_eq!(device.id(), "usb:1:8"),
+            res => panic!("unexpected {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_unsupported_transport() {
+        use crate::transport::udp::AvailableUdpTransport;
+
+        let emulator = AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: "127.0.0.1".to_owned(),
+                port: 21324,
+            }),
+        };
+        let res = futures::executor::block_on(wait_for_bootloader(&emulator, &[]));
+        assert!(matches!(res, Err(Error::UnsupportedReboot)));
+    }
+}
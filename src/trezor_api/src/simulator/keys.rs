@@ -0,0 +1,282 @@
+//! Key derivations of the simulator: BIP-39 seeds, BIP-32 for secp256k1
+//! and SLIP-10 for ed25519.
+
+use blake2::digest::consts::U20;
+use blake2::Blake2b;
+use hmac::{Hmac, Mac};
+use ripemd::Ripemd160;
+use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::protos::HARDENED_PATH;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Base58check prefix of Tezos `tz1` addresses.
+const TEZOS_ED25519_ADDRESS_PREFIX: [u8; 3] = [6, 161, 159];
+/// Base58check prefix of Tezos `edpk` public keys.
+const TEZOS_ED25519_PUBLIC_KEY_PREFIX: [u8; 4] = [13, 15, 37, 217];
+
+/// Derive the BIP-39 seed from a mnemonic and a passphrase.
+///
+/// The mnemonic is not checked against the wordlist and not NFKD-normalized,
+/// so it must be in plain ASCII.
+pub fn seed_from_mnemonic(mnemonic: &str, passphrase: &str) -> [u8; 64] {
+    let salt = format!("mnemonic{}", passphrase);
+    let mut seed = [0; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(mnemonic.as_bytes(), salt.as_bytes(), 2048, &mut seed);
+    seed
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in data {
+        mac.update(part);
+    }
+    let mut out = [0; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+/// `RIPEMD160(SHA256(data))`.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    let mut out = [0; 20];
+    out.copy_from_slice(&Ripemd160::digest(Sha256::digest(data)));
+    out
+}
+
+/// The path contains an index the curve can't derive, or one with no valid child key,
+/// or is deeper than 255 levels.
+#[derive(Debug)]
+pub struct ForbiddenPath;
+
+/// A BIP-32 node on the secp256k1 curve.
+#[derive(Clone)]
+pub struct Secp256k1Node {
+    pub depth: u8,
+    pub fingerprint: u32,
+    pub child_num: u32,
+    pub chain_code: [u8; 32],
+    pub secret_key: SecretKey,
+}
+
+impl Secp256k1Node {
+    pub fn master(seed: &[u8]) -> Secp256k1Node {
+        let i = hmac_sha512(b"Bitcoin seed", &[seed]);
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&i[32..]);
+        Secp256k1Node {
+            depth: 0,
+            fingerprint: 0,
+            child_num: 0,
+            chain_code,
+            secret_key: SecretKey::from_slice(&i[..32]).expect("a valid master key"),
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_secret_key(&Secp256k1::signing_only(), &self.secret_key)
+    }
+
+    pub fn derive(&self, index: u32) -> Result<Secp256k1Node, ForbiddenPath> {
+        // The depth is serialized in a single byte.
+        let depth = self.depth.checked_add(1).ok_or(ForbiddenPath)?;
+        let index_bytes = index.to_be_bytes();
+        let i = if index >= HARDENED_PATH {
+            hmac_sha512(
+                &self.chain_code,
+                &[&[0], &self.secret_key.secret_bytes(), &index_bytes],
+            )
+        } else {
+            hmac_sha512(
+                &self.chain_code,
+                &[&self.public_key().serialize(), &index_bytes],
+            )
+        };
+
+        let mut tweak = [0; 32];
+        tweak.copy_from_slice(&i[..32]);
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&i[32..]);
+
+        // BIP-32 leaves the index without a child key if the tweak isn't below the curve
+        // order or the key is zero, the next index is to be used then. The probability is
+        // lower than 1 in 2^127.
+        let secret_key = Scalar::from_be_bytes(tweak)
+            .ok()
+            .and_then(|tweak| self.secret_key.add_tweak(&tweak).ok())
+            .ok_or(ForbiddenPath)?;
+
+        let parent_id = hash160(&self.public_key().serialize());
+        Ok(Secp256k1Node {
+            depth,
+            fingerprint: u32::from_be_bytes([
+                parent_id[0],
+                parent_id[1],
+                parent_id[2],
+                parent_id[3],
+            ]),
+            child_num: index,
+            chain_code,
+            secret_key,
+        })
+    }
+
+    pub fn derive_path(&self, path: &[u32]) -> Result<Secp256k1Node, ForbiddenPath> {
+        path.iter()
+            .try_fold(self.clone(), |node, index| node.derive(*index))
+    }
+
+    /// Serialize the public node with the given version bytes, f.e. as `xpub`.
+    pub fn serialize_public(&self, version: u32) -> String {
+        let mut data = Vec::with_capacity(78);
+        data.extend(&version.to_be_bytes());
+        data.push(self.depth);
+        data.extend(&self.fingerprint.to_be_bytes());
+        data.extend(&self.child_num.to_be_bytes());
+        data.extend(&self.chain_code);
+        data.extend(&self.public_key().serialize());
+        bs58::encode(data).with_check().into_string()
+    }
+
+    /// The legacy P2PKH address.
+    pub fn p2pkh_address(&self, address_type: u8) -> String {
+        let mut data = vec![address_type];
+        data.extend(&hash160(&self.public_key().serialize()));
+        bs58::encode(data).with_check().into_string()
+    }
+
+    /// The SegWit address nested in P2SH.
+    pub fn p2sh_p2wpkh_address(&self, address_type_p2sh: u8) -> String {
+        let mut redeem_script = vec![0x00, 0x14];
+        redeem_script.extend(&hash160(&self.public_key().serialize()));
+        let mut data = vec![address_type_p2sh];
+        data.extend(&hash160(&redeem_script));
+        bs58::encode(data).with_check().into_string()
+    }
+}
+
+/// A SLIP-10 node on the ed25519 curve.
+#[derive(Clone)]
+pub struct Ed25519Node {
+    pub chain_code: [u8; 32],
+    pub secret_key: [u8; 32],
+}
+
+impl Ed25519Node {
+    pub fn master(seed: &[u8]) -> Ed25519Node {
+        let i = hmac_sha512(b"ed25519 seed", &[seed]);
+        Ed25519Node::from_hmac(&i)
+    }
+
+    fn from_hmac(i: &[u8; 64]) -> Ed25519Node {
+        let mut secret_key = [0; 32];
+        secret_key.copy_from_slice(&i[..32]);
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&i[32..]);
+        Ed25519Node {
+            chain_code,
+            secret_key,
+        }
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        ed25519_dalek::SigningKey::from_bytes(&self.secret_key)
+            .verifying_key()
+            .to_bytes()
+    }
+
+    /// Only hardened derivation is defined for ed25519.
+    pub fn derive(&self, index: u32) -> Result<Ed25519Node, ForbiddenPath> {
+        if index < HARDENED_PATH {
+            return Err(ForbiddenPath);
+        }
+        let i = hmac_sha512(
+            &self.chain_code,
+            &[&[0], &self.secret_key, &index.to_be_bytes()],
+        );
+        Ok(Ed25519Node::from_hmac(&i))
+    }
+
+    pub fn derive_path(&self, path: &[u32]) -> Result<Ed25519Node, ForbiddenPath> {
+        path.iter()
+            .try_fold(self.clone(), |node, index| node.derive(*index))
+    }
+
+    /// The Tezos `tz1` address.
+    pub fn tezos_address(&self) -> String {
+        let key_hash = Blake2b::<U20>::digest(self.public_key());
+        let mut data = TEZOS_ED25519_ADDRESS_PREFIX.to_vec();
+        data.extend(key_hash.as_slice());
+        bs58::encode(data).with_check().into_string()
+    }
+
+    /// The Tezos `edpk` public key.
+    pub fn tezos_public_key(&self) -> String {
+        let mut data = TEZOS_ED25519_PUBLIC_KEY_PREFIX.to_vec();
+        data.extend(&self.public_key());
+        bs58::encode(data).with_check().into_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const H: u32 = HARDENED_PATH;
+
+    /// The seed of the test vectors 1 of BIP-32 and SLIP-10.
+    fn test_seed() -> Vec<u8> {
+        (0..16).collect()
+    }
+
+    #[test]
+    fn test_bip32_vector_1() {
+        let master = Secp256k1Node::master(&test_seed());
+        assert_eq!(
+            master.serialize_public(0x0488_b21e),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        );
+        assert_eq!(
+            master
+                .derive_path(&[H, 1, 2 + H])
+                .unwrap()
+                .serialize_public(0x0488_b21e),
+            "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5"
+        );
+    }
+
+    #[test]
+    fn test_bip32_max_depth() {
+        let node = Secp256k1Node::master(&test_seed())
+            .derive_path(&[0; 255])
+            .unwrap();
+        assert_eq!(node.depth, 255);
+        assert!(node.derive(0).is_err());
+    }
+
+    #[test]
+    fn test_slip10_ed25519_vector_1() {
+        let master = Ed25519Node::master(&test_seed());
+        let node = master.derive_path(&[H, 1 + H]).unwrap();
+        assert_eq!(
+            hex(&node.public_key()),
+            "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187"
+        );
+        assert!(master.derive_path(&[H, 1]).is_err());
+    }
+
+    #[test]
+    fn test_bip39_seed() {
+        // The first test vector of the reference implementation.
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(
+            hex(&seed_from_mnemonic(mnemonic, "TREZOR")),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    fn hex(data: &[u8]) -> String {
+        data.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
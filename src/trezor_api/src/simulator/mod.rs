@@ -0,0 +1,706 @@
+//! # Software simulator
+//!
+//! A software Trezor speaking the original binary protocol over UDP, like the
+//! official emulator. It can be connected to with [UdpTransport](crate::transport::udp::UdpTransport),
+//! so applications can be tested end-to-end without a device.
+//!
+//! The simulator holds a BIP-39 seed and answers `Initialize`, `GetFeatures`, `Ping`,
+//! `GetAddress`, `GetPublicKey`, `TezosGetAddress` and `TezosGetPublicKey` with real
+//! derivations. It can be configured to protect the calls with a PIN and to ask
+//! for button confirmations. Every other message is answered with a `Failure`.
+//!
+//! # Example
+//! ```no_run
+//! # use trezor_api::simulator::{Simulator, SimulatorConfig};
+//! let simulator = Simulator::bind("127.0.0.1:0", SimulatorConfig::default())
+//!     .unwrap()
+//!     .spawn();
+//!
+//! let trezor = simulator.available_device().unwrap().connect().unwrap();
+//! ```
+
+use byteorder::{BigEndian, ByteOrder};
+use protobuf::{Message, ProtobufEnum};
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::messages::TrezorMessage;
+use crate::protos::{self, MessageType};
+use crate::transport::udp::{AvailableUdpTransport, PING_DATAGRAM, PONG_DATAGRAM};
+use crate::transport::{v1_chunks, AvailableDeviceTransport, ProtoMessage};
+use crate::{AvailableDevice, DeviceMode, TrezorModel};
+
+mod keys;
+
+pub use keys::{seed_from_mnemonic, Ed25519Node, Secp256k1Node};
+
+/// The chunk size for the serial protocol.
+const CHUNK_SIZE: usize = 64;
+
+/// How often the serving thread checks whether it should stop.
+const STOP_POLL_INTERVAL_MS: u64 = 100;
+
+/// The mnemonic the official test suites use.
+pub const TEST_MNEMONIC: &str = "all all all all all all all all all all all all";
+
+/// The configuration of a [Simulator].
+#[derive(Clone, Debug)]
+pub struct SimulatorConfig {
+    /// The BIP-39 mnemonic to derive keys from.
+    pub mnemonic: String,
+    /// The BIP-39 passphrase.
+    pub passphrase: String,
+    /// Protect the key derivations with this PIN.
+    ///
+    /// The PIN matrix is not scrambled, so the PIN is acked with its digits as they are.
+    pub pin: Option<String>,
+    /// Ask for a button confirmation before every key derivation.
+    pub button_protection: bool,
+    pub label: String,
+    pub model: TrezorModel,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        SimulatorConfig {
+            mnemonic: TEST_MNEMONIC.to_owned(),
+            passphrase: String::new(),
+            pin: None,
+            button_protection: false,
+            label: "Simulator".to_owned(),
+            model: TrezorModel::T,
+        }
+    }
+}
+
+/// The parameters of the coins supported by `GetAddress` and `GetPublicKey`.
+struct CoinInfo {
+    name: &'static str,
+    address_type: u8,
+    address_type_p2sh: u8,
+    xpub_magic: u32,
+}
+
+const COINS: &[CoinInfo] = &[
+    CoinInfo {
+        name: "Bitcoin",
+        address_type: 0,
+        address_type_p2sh: 5,
+        xpub_magic: 0x0488_b21e,
+    },
+    CoinInfo {
+        name: "Testnet",
+        address_type: 111,
+        address_type_p2sh: 196,
+        xpub_magic: 0x0435_87cf,
+    },
+    CoinInfo {
+        name: "Komodo",
+        address_type: 60,
+        address_type_p2sh: 85,
+        xpub_magic: 0xf9ee_e48d,
+    },
+];
+
+/// The interaction the simulated device is waiting for.
+enum Interaction {
+    Pin,
+    Button,
+}
+
+/// A request waiting for the user to interact with the simulated device.
+struct Pending {
+    request: ProtoMessage,
+    interaction: Interaction,
+}
+
+/// The state of the simulated device.
+struct Device {
+    config: SimulatorConfig,
+    seed: [u8; 64],
+    unlocked: bool,
+    session_id: Vec<u8>,
+    session_counter: u32,
+    pending: Option<Pending>,
+}
+
+fn encode<M: TrezorMessage>(message: M) -> ProtoMessage {
+    let payload = message
+        .write_to_bytes()
+        .expect("the simulator sends complete messages");
+    ProtoMessage(M::message_type(), payload)
+}
+
+fn failure(code: protos::Failure_FailureType, message: &str) -> ProtoMessage {
+    let mut resp = protos::Failure::new();
+    resp.set_code(code);
+    resp.set_message(message.to_owned());
+    encode(resp)
+}
+
+fn data_error(message: &str) -> ProtoMessage {
+    failure(protos::Failure_FailureType::Failure_DataError, message)
+}
+
+impl Device {
+    fn new(config: SimulatorConfig) -> Device {
+        let seed = seed_from_mnemonic(&config.mnemonic, &config.passphrase);
+        let unlocked = config.pin.is_none();
+        let mut device = Device {
+            config,
+            seed,
+            unlocked,
+            session_id: Vec::new(),
+            session_counter: 0,
+            pending: None,
+        };
+        device.new_session();
+        device
+    }
+
+    fn new_session(&mut self) {
+        self.session_counter += 1;
+        let mut session_id = vec![0; 32];
+        BigEndian::write_u32(&mut session_id[28..], self.session_counter);
+        self.session_id = session_id;
+    }
+
+    fn features(&self) -> protos::Features {
+        let mut features = protos::Features::new();
+        features.set_vendor("trezor.io".to_owned());
+        let (major, minor, patch, model) = match self.config.model {
+            TrezorModel::One => (1, 10, 0, "1"),
+            TrezorModel::T => (2, 4, 0, "T"),
+        };
+        features.set_major_version(major);
+        features.set_minor_version(minor);
+        features.set_patch_version(patch);
+        features.set_model(model.to_owned());
+        features.set_bootloader_mode(false);
+        features.set_device_id("SIMULATOR".to_owned());
+        features.set_label(self.config.label.clone());
+        features.set_initialized(true);
+        features.set_pin_protection(self.config.pin.is_some());
+        features.set_passphrase_protection(false);
+        features.set_unlocked(self.unlocked);
+        features.set_session_id(self.session_id.clone());
+        features.set_capabilities(vec![
+            protos::Features_Capability::Capability_Bitcoin,
+            protos::Features_Capability::Capability_Bitcoin_like,
+            protos::Features_Capability::Capability_Tezos,
+        ]);
+        features
+    }
+
+    /// Handle a message from the host and produce the response.
+    fn handle(&mut self, message: ProtoMessage) -> ProtoMessage {
+        match message.message_type() {
+            MessageType::MessageType_Initialize => {
+                self.pending = None;
+                let req: protos::Initialize = match message.into_message() {
+                    Ok(req) => req,
+                    Err(_) => return data_error("Invalid Initialize"),
+                };
+                if req.get_session_id() != &self.session_id[..] {
+                    self.new_session();
+                }
+                encode(self.features())
+            }
+            MessageType::MessageType_GetFeatures => encode(self.features()),
+            MessageType::MessageType_Cancel => match self.pending.take() {
+                Some(Pending {
+                    interaction: Interaction::Pin,
+                    ..
+                }) => failure(
+                    protos::Failure_FailureType::Failure_PinCancelled,
+                    "PIN entry cancelled",
+                ),
+                _ => failure(
+                    protos::Failure_FailureType::Failure_ActionCancelled,
+                    "Cancelled",
+                ),
+            },
+            MessageType::MessageType_PinMatrixAck => match self.pending.take() {
+                Some(Pending {
+                    request,
+                    interaction: Interaction::Pin,
+                }) => {
+                    let pin: protos::PinMatrixAck = match message.into_message() {
+                        Ok(ack) => ack,
+                        Err(_) => return data_error("Invalid PinMatrixAck"),
+                    };
+                    if Some(pin.get_pin()) != self.config.pin.as_deref() {
+                        return failure(
+                            protos::Failure_FailureType::Failure_PinInvalid,
+                            "PIN invalid",
+                        );
+                    }
+                    self.unlocked = true;
+                    self.confirm(request)
+                }
+                _ => failure(
+                    protos::Failure_FailureType::Failure_UnexpectedMessage,
+                    "Unexpected message",
+                ),
+            },
+            MessageType::MessageType_ButtonAck => match self.pending.take() {
+                Some(Pending {
+                    request,
+                    interaction: Interaction::Button,
+                }) => self.answer(request),
+                _ => failure(
+                    protos::Failure_FailureType::Failure_UnexpectedMessage,
+                    "Unexpected message",
+                ),
+            },
+            MessageType::MessageType_Ping => self.confirm(message),
+            MessageType::MessageType_GetAddress
+            | MessageType::MessageType_GetPublicKey
+            | MessageType::MessageType_TezosGetAddress
+            | MessageType::MessageType_TezosGetPublicKey => {
+                self.pending = None;
+                if !self.unlocked {
+                    self.pending = Some(Pending {
+                        request: message,
+                        interaction: Interaction::Pin,
+                    });
+                    let mut req = protos::PinMatrixRequest::new();
+                    req.set_field_type(
+                        protos::PinMatrixRequest_PinMatrixRequestType::PinMatrixRequestType_Current,
+                    );
+                    return encode(req);
+                }
+                self.confirm(message)
+            }
+            _ => failure(
+                protos::Failure_FailureType::Failure_UnexpectedMessage,
+                "Unexpected message",
+            ),
+        }
+    }
+
+    /// Ask for a button confirmation if the request needs one, or answer it.
+    fn confirm(&mut self, request: ProtoMessage) -> ProtoMessage {
+        let code = match request.message_type() {
+            MessageType::MessageType_Ping => {
+                let protected = protos::Ping::parse_from_bytes(request.payload())
+                    .map(|ping| ping.get_button_protection())
+                    .unwrap_or(false);
+                if !protected {
+                    return self.answer(request);
+                }
+                protos::ButtonRequest_ButtonRequestType::ButtonRequest_ProtectCall
+            }
+            _ if !self.config.button_protection => return self.answer(request),
+            MessageType::MessageType_GetPublicKey | MessageType::MessageType_TezosGetPublicKey => {
+                protos::ButtonRequest_ButtonRequestType::ButtonRequest_PublicKey
+            }
+            _ => protos::ButtonRequest_ButtonRequestType::ButtonRequest_Address,
+        };
+
+        self.pending = Some(Pending {
+            request,
+            interaction: Interaction::Button,
+        });
+        let mut req = protos::ButtonRequest::new();
+        req.set_code(code);
+        encode(req)
+    }
+
+    /// Answer a request the user has agreed to.
+    fn answer(&mut self, request: ProtoMessage) -> ProtoMessage {
+        let result = match request.message_type() {
+            MessageType::MessageType_Ping => request
+                .into_message()
+                .map(|req: protos::Ping| self.ping(req)),
+            MessageType::MessageType_GetAddress => request
+                .into_message()
+                .map(|req: protos::GetAddress| self.get_address(req)),
+            MessageType::MessageType_GetPublicKey => request
+                .into_message()
+                .map(|req: protos::GetPublicKey| self.get_public_key(req)),
+            MessageType::MessageType_TezosGetAddress => request
+                .into_message()
+                .map(|req: protos::TezosGetAddress| self.tezos_get_address(req)),
+            MessageType::MessageType_TezosGetPublicKey => request
+                .into_message()
+                .map(|req: protos::TezosGetPublicKey| self.tezos_get_public_key(req)),
+            _ => {
+                return failure(
+                    protos::Failure_FailureType::Failure_UnexpectedMessage,
+                    "Unexpected message",
+                )
+            }
+        };
+        result.unwrap_or_else(|_| data_error("Invalid message"))
+    }
+
+    fn ping(&self, req: protos::Ping) -> ProtoMessage {
+        let mut resp = protos::Success::new();
+        resp.set_message(req.get_message().to_owned());
+        encode(resp)
+    }
+
+    fn coin(&self, name: &str) -> Option<&'static CoinInfo> {
+        COINS.iter().find(|coin| coin.name == name)
+    }
+
+    fn get_address(&self, req: protos::GetAddress) -> ProtoMessage {
+        let coin = match self.coin(req.get_coin_name()) {
+            Some(coin) => coin,
+            None => return data_error("Invalid coin name"),
+        };
+        let node = match Secp256k1Node::master(&self.seed).derive_path(req.get_address_n()) {
+            Ok(node) => node,
+            Err(_) => return data_error("Forbidden key path"),
+        };
+        let address = match req.get_script_type() {
+            protos::InputScriptType::SPENDADDRESS => node.p2pkh_address(coin.address_type),
+            protos::InputScriptType::SPENDP2SHWITNESS => {
+                node.p2sh_p2wpkh_address(coin.address_type_p2sh)
+            }
+            _ => return data_error("Unsupported script type"),
+        };
+
+        let mut resp = protos::Address::new();
+        resp.set_address(address);
+        encode(resp)
+    }
+
+    fn get_public_key(&self, req: protos::GetPublicKey) -> ProtoMessage {
+        let coin = match self.coin(req.get_coin_name()) {
+            Some(coin) => coin,
+            None => return data_error("Invalid coin name"),
+        };
+        if req.has_ecdsa_curve_name() && req.get_ecdsa_curve_name() != "secp256k1" {
+            return data_error("Unsupported curve");
+        }
+        let master = Secp256k1Node::master(&self.seed);
+        let node = match master.derive_path(req.get_address_n()) {
+            Ok(node) => node,
+            Err(_) => return data_error("Forbidden key path"),
+        };
+
+        let mut hd_node = protos::HDNodeType::new();
+        hd_node.set_depth(node.depth as u32);
+        hd_node.set_fingerprint(node.fingerprint);
+        hd_node.set_child_num(node.child_num);
+        hd_node.set_chain_code(node.chain_code.to_vec());
+        hd_node.set_public_key(node.public_key().serialize().to_vec());
+
+        let root_id = keys::hash160(&master.public_key().serialize());
+        let mut resp = protos::PublicKey::new();
+        resp.set_xpub(node.serialize_public(coin.xpub_magic));
+        resp.set_node(hd_node);
+        resp.set_root_fingerprint(BigEndian::read_u32(&root_id[..4]));
+        encode(resp)
+    }
+
+    fn tezos_get_address(&self, req: protos::TezosGetAddress) -> ProtoMessage {
+        match Ed25519Node::master(&self.seed).derive_path(req.get_address_n()) {
+            Ok(node) => {
+                let mut resp = protos::TezosAddress::new();
+                resp.set_address(node.tezos_address());
+                encode(resp)
+            }
+            Err(_) => data_error("Forbidden key path"),
+        }
+    }
+
+    fn tezos_get_public_key(&self, req: protos::TezosGetPublicKey) -> ProtoMessage {
+        match Ed25519Node::master(&self.seed).derive_path(req.get_address_n()) {
+            Ok(node) => {
+                let mut resp = protos::TezosPublicKey::new();
+                resp.set_public_key(node.tezos_public_key());
+                encode(resp)
+            }
+            Err(_) => data_error("Forbidden key path"),
+        }
+    }
+}
+
+/// A software Trezor serving the emulator UDP protocol.
+pub struct Simulator {
+    socket: UdpSocket,
+    device: Device,
+    /// The data of the messages being received from each peer, without the chunk prefixes.
+    received: HashMap<SocketAddr, Vec<u8>>,
+}
+
+impl Simulator {
+    /// Bind the simulator to the given address, f.e. `127.0.0.1:0` for an ephemeral port.
+    pub fn bind(address: &str, config: SimulatorConfig) -> io::Result<Simulator> {
+        let socket = UdpSocket::bind(address)?;
+        Ok(Simulator {
+            socket,
+            device: Device::new(config),
+            received: HashMap::new(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Serve requests on the current thread until `stop` is set.
+    pub fn run(mut self, stop: &AtomicBool) -> io::Result<()> {
+        self.socket
+            .set_read_timeout(Some(Duration::from_millis(STOP_POLL_INTERVAL_MS)))?;
+
+        let mut buf = [0; CHUNK_SIZE];
+        while !stop.load(Ordering::Relaxed) {
+            let (n, peer) = match self.socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(ref e)
+                    if e.kind() == io::ErrorKind::WouldBlock
+                        || e.kind() == io::ErrorKind::TimedOut =>
+                {
+                    continue
+                }
+                Err(e) => return Err(e),
+            };
+
+            if &buf[..n] == PING_DATAGRAM {
+                self.socket.send_to(PONG_DATAGRAM, peer)?;
+                continue;
+            }
+            if let Some(resp) = self.receive_chunk(peer, &buf[..n]) {
+                for chunk in v1_chunks(resp) {
+                    self.socket.send_to(&chunk, peer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve requests on a new thread until the returned handle is dropped.
+    pub fn spawn(self) -> SimulatorHandle {
+        let address = self.local_addr().ok();
+        let model = self.device.config.model;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || self.run(&thread_stop));
+        SimulatorHandle {
+            address,
+            model,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Collect a chunk from the peer and handle the message once it is complete.
+    ///
+    /// Chunks of other protocols, f.e. a V2 session begin, are answered with a
+    /// `Failure` by Trezor Model T and dropped by Trezor One, like the real devices do.
+    fn receive_chunk(&mut self, peer: SocketAddr, chunk: &[u8]) -> Option<ProtoMessage> {
+        if chunk.len() != CHUNK_SIZE {
+            return None;
+        }
+        let first = !self.received.contains_key(&peer);
+        if chunk[0] != 0x3f || (first && chunk[1..3] != [0x23, 0x23]) {
+            return match self.device.config.model {
+                TrezorModel::T if first => Some(failure(
+                    protos::Failure_FailureType::Failure_UnexpectedMessage,
+                    "Invalid magic",
+                )),
+                _ => None,
+            };
+        }
+        let received = self.received.entry(peer).or_default();
+        received.extend(&chunk[1..]);
+
+        let data_length = BigEndian::read_u32(&received[4..8]) as usize;
+        if received.len() < 8 + data_length {
+            return None;
+        }
+        let received = self.received.remove(&peer).unwrap_or_default();
+        let message_type_id = BigEndian::read_u16(&received[2..4]) as i32;
+        let payload = received[8..8 + data_length].to_vec();
+
+        let resp = match MessageType::from_i32(message_type_id) {
+            Some(message_type) => self.device.handle(ProtoMessage(message_type, payload)),
+            None => failure(
+                protos::Failure_FailureType::Failure_UnexpectedMessage,
+                "Unknown message",
+            ),
+        };
+        Some(resp)
+    }
+}
+
+/// A simulator serving on a background thread. It is stopped on drop.
+pub struct SimulatorHandle {
+    address: Option<SocketAddr>,
+    model: TrezorModel,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl SimulatorHandle {
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.address
+    }
+
+    /// The simulator as a device that can be connected to, `None` if its address is unknown.
+    pub fn available_device(&self) -> Option<AvailableDevice> {
+        let address = self.address?;
+        Some(AvailableDevice {
+            model: self.model,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: address.ip().to_string(),
+                port: address.port() as usize,
+            }),
+        })
+    }
+}
+
+impl Drop for SimulatorHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos::HARDENED_PATH as H;
+
+    fn get_address(path: Vec<u32>) -> ProtoMessage {
+        let mut req = protos::GetAddress::new();
+        req.set_coin_name("Bitcoin".to_owned());
+        req.set_address_n(path);
+        encode(req)
+    }
+
+    #[test]
+    fn test_get_address() {
+        let mut device = Device::new(SimulatorConfig::default());
+        let resp = device.handle(get_address(vec![44 + H, H, H, 0, 0]));
+        let address: protos::Address = resp.into_message().unwrap();
+        assert_eq!(address.get_address(), "1JAd7XCBzGudGpJQSDSfpmJhiygtLQWaGL");
+    }
+
+    #[test]
+    fn test_get_address_too_deep() {
+        let mut device = Device::new(SimulatorConfig::default());
+        let resp = device.handle(get_address(vec![0; 256]));
+        let failure: protos::Failure = resp.into_message().unwrap();
+        assert_eq!(
+            failure.get_code(),
+            protos::Failure_FailureType::Failure_DataError
+        );
+    }
+
+    #[test]
+    fn test_pin_and_button() {
+        let mut device = Device::new(SimulatorConfig {
+            pin: Some("1234".to_owned()),
+            button_protection: true,
+            ..SimulatorConfig::default()
+        });
+        let request = get_address(vec![44 + H, H, H, 0, 0]);
+
+        let resp = device.handle(request.clone());
+        assert_eq!(
+            resp.message_type(),
+            MessageType::MessageType_PinMatrixRequest
+        );
+        let mut ack = protos::PinMatrixAck::new();
+        ack.set_pin("4321".to_owned());
+        let resp = device.handle(encode(ack));
+        assert_eq!(resp.message_type(), MessageType::MessageType_Failure);
+
+        device.handle(request);
+        let mut ack = protos::PinMatrixAck::new();
+        ack.set_pin("1234".to_owned());
+        let resp = device.handle(encode(ack));
+        assert_eq!(resp.message_type(), MessageType::MessageType_ButtonRequest);
+        let resp = device.handle(encode(protos::ButtonAck::new()));
+        assert_eq!(resp.message_type(), MessageType::MessageType_Address);
+    }
+
+    #[test]
+    fn test_interleaved_peers() {
+        let mut simulator = Simulator::bind("127.0.0.1:0", SimulatorConfig::default()).unwrap();
+        let peers: Vec<SocketAddr> = vec![
+            "127.0.0.1:1".parse().unwrap(),
+            "127.0.0.1:2".parse().unwrap(),
+        ];
+        let chunks: Vec<Vec<Vec<u8>>> = ["a", "b"]
+            .iter()
+            .map(|c| {
+                let mut ping = protos::Ping::new();
+                ping.set_message(c.repeat(100));
+                v1_chunks(encode(ping))
+            })
+            .collect();
+        assert_eq!(chunks[0].len(), 2);
+
+        // The first chunks of both messages arrive before the second ones.
+        for (peer, chunks) in peers.iter().zip(&chunks) {
+            assert!(simulator.receive_chunk(*peer, &chunks[0]).is_none());
+        }
+        for (peer, (chunks, c)) in peers.iter().zip(chunks.iter().zip(&["a", "b"])) {
+            let resp = simulator.receive_chunk(*peer, &chunks[1]).unwrap();
+            let success: protos::Success = resp.into_message().unwrap();
+            assert_eq!(success.get_message(), c.repeat(100));
+        }
+    }
+
+    #[test]
+    fn test_udp_connection() {
+        let simulator = Simulator::bind("127.0.0.1:0", SimulatorConfig::default())
+            .unwrap()
+            .spawn();
+
+        let mut trezor = simulator.available_device().unwrap().connect().unwrap();
+        futures::executor::block_on(async {
+            trezor.init_device().await?;
+            trezor.ping("hello").await?.ok()
+        })
+        .unwrap();
+        assert_eq!(trezor.features().unwrap().get_label(), "Simulator");
+    }
+
+    #[test]
+    fn test_available_device_model() {
+        let config = SimulatorConfig {
+            model: TrezorModel::One,
+            ..SimulatorConfig::default()
+        };
+        let simulator = Simulator::bind("127.0.0.1:0", config).unwrap().spawn();
+        assert_eq!(
+            simulator.available_device().unwrap().model,
+            TrezorModel::One
+        );
+    }
+
+    #[test]
+    fn test_udp_protocol_v2_probe() {
+        let simulator = Simulator::bind("127.0.0.1:0", SimulatorConfig::default())
+            .unwrap()
+            .spawn();
+
+        // The simulator answers the V2 session begin with a V1 `Failure`, like trezor-core.
+        let mut trezor = simulator.available_device().unwrap().connect().unwrap();
+        trezor.set_transport_config(crate::transport::TransportConfig {
+            protocol_v2: true,
+            ..Default::default()
+        });
+        futures::executor::block_on(async {
+            trezor.init_device().await?;
+            trezor.ping("hello").await?.ok()
+        })
+        .unwrap();
+    }
+}
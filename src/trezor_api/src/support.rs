@@ -0,0 +1,192 @@
+//! # Capabilities and firmware versions
+//!
+//! The firmwares don't handle every message: the Bitcoin-only firmware and Trezor Model
+//! One lack most coins, and the newer messages need a recent firmware. The calls the
+//! device can't handle, going by its `Features`, fail early with [Error::Unsupported]
+//! instead of an opaque failure from the device.
+//!
+//! [Error::Unsupported]: crate::Error::Unsupported
+
+use std::fmt;
+
+use crate::protos::{Features, Features_Capability, MessageType};
+use crate::TrezorModel;
+
+/// A capability of the firmware, as reported in the `Features`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Capability {
+    Bitcoin,
+    BitcoinLike,
+    Binance,
+    Cardano,
+    Crypto,
+    EOS,
+    Ethereum,
+    Lisk,
+    Monero,
+    NEM,
+    Ripple,
+    Stellar,
+    Tezos,
+    U2F,
+    Shamir,
+    ShamirGroups,
+    PassphraseEntry,
+}
+
+impl From<Features_Capability> for Capability {
+    fn from(capability: Features_Capability) -> Capability {
+        use Features_Capability::*;
+
+        match capability {
+            Capability_Bitcoin => Capability::Bitcoin,
+            Capability_Bitcoin_like => Capability::BitcoinLike,
+            Capability_Binance => Capability::Binance,
+            Capability_Cardano => Capability::Cardano,
+            Capability_Crypto => Capability::Crypto,
+            Capability_EOS => Capability::EOS,
+            Capability_Ethereum => Capability::Ethereum,
+            Capability_Lisk => Capability::Lisk,
+            Capability_Monero => Capability::Monero,
+            Capability_NEM => Capability::NEM,
+            Capability_Ripple => Capability::Ripple,
+            Capability_Stellar => Capability::Stellar,
+            Capability_Tezos => Capability::Tezos,
+            Capability_U2F => Capability::U2F,
+            Capability_Shamir => Capability::Shamir,
+            Capability_ShamirGroups => Capability::ShamirGroups,
+            Capability_PassphraseEntry => Capability::PassphraseEntry,
+        }
+    }
+}
+
+/// The version of the firmware.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+pub struct FirmwareVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FirmwareVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> FirmwareVersion {
+        FirmwareVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Get the version reported in the features.
+    pub fn from_features(features: &Features) -> FirmwareVersion {
+        FirmwareVersion::new(
+            features.get_major_version(),
+            features.get_minor_version(),
+            features.get_patch_version(),
+        )
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// What a call needs from the device.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Requirement {
+    /// The firmware reports the capability.
+    Capability(Capability),
+    /// The firmware is at least this version.
+    Version(FirmwareVersion),
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Requirement::Capability(capability) => write!(f, "the {:?} capability", capability),
+            Requirement::Version(version) => write!(f, "firmware {}", version),
+        }
+    }
+}
+
+/// The prefixes of the message types of each coin, f.e. `TezosGetAddress`.
+const CAPABILITY_PREFIXES: &[(&str, Capability)] = &[
+    ("Binance", Capability::Binance),
+    ("Cardano", Capability::Cardano),
+    ("Eos", Capability::EOS),
+    ("Ethereum", Capability::Ethereum),
+    ("Lisk", Capability::Lisk),
+    ("Monero", Capability::Monero),
+    ("NEM", Capability::NEM),
+    ("Ripple", Capability::Ripple),
+    ("Stellar", Capability::Stellar),
+    ("Tezos", Capability::Tezos),
+];
+
+/// The capability the firmware needs to handle the message type.
+pub fn required_capability(message_type: MessageType) -> Option<Capability> {
+    let name = format!("{:?}", message_type);
+    let name = name.trim_start_matches("MessageType_");
+    CAPABILITY_PREFIXES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|&(_, capability)| capability)
+}
+
+/// The firmware versions that introduced the messages, on Trezor Model One and
+/// Trezor Model T.
+const MINIMUM_VERSIONS: &[(MessageType, FirmwareVersion, FirmwareVersion)] = &[
+    (
+        MessageType::MessageType_ChangeWipeCode,
+        FirmwareVersion::new(1, 9, 0),
+        FirmwareVersion::new(2, 3, 0),
+    ),
+    (
+        MessageType::MessageType_EthereumSignTxEIP1559,
+        FirmwareVersion::new(1, 10, 4),
+        FirmwareVersion::new(2, 4, 2),
+    ),
+];
+
+/// The firmware version the model needs to handle the message type.
+pub fn minimum_version(message_type: MessageType, model: TrezorModel) -> Option<FirmwareVersion> {
+    MINIMUM_VERSIONS
+        .iter()
+        .find(|(t, _, _)| *t == message_type)
+        .map(|&(_, one, t)| match model {
+            TrezorModel::One => one,
+            TrezorModel::T => t,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos::MessageType::*;
+
+    #[test]
+    fn test_required_capability() {
+        assert_eq!(
+            required_capability(MessageType_TezosSignTx),
+            Some(Capability::Tezos)
+        );
+        assert_eq!(
+            required_capability(MessageType_EthereumSignTxEIP1559),
+            Some(Capability::Ethereum)
+        );
+        assert_eq!(required_capability(MessageType_GetAddress), None);
+    }
+
+    #[test]
+    fn test_minimum_version() {
+        assert_eq!(
+            minimum_version(MessageType_EthereumSignTxEIP1559, TrezorModel::One),
+            Some(FirmwareVersion::new(1, 10, 4))
+        );
+        assert_eq!(minimum_version(MessageType_Ping, TrezorModel::T), None);
+        assert!(FirmwareVersion::new(2, 4, 2) > FirmwareVersion::new(2, 3, 10));
+        assert_eq!(FirmwareVersion::new(2, 4, 2).to_string(), "2.4.2");
+    }
+}
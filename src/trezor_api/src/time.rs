@@ -0,0 +1,47 @@
+//! Executor-agnostic timers and the wall clock.
+
+use std::time::Duration;
+
+/// Suspend the current task for the given duration.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
+/// Suspend the current task for the given duration.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+    use js_sys::Promise;
+    use wasm_bindgen_futures::JsFuture;
+
+    let millis = duration.as_millis() as i32;
+    let promise = Promise::new(&mut |resolve, _reject| {
+        set_timeout(&resolve, millis);
+    });
+    // `setTimeout` never rejects.
+    JsFuture::from(promise).await.ok();
+}
+
+/// The current time in milliseconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn unix_millis() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The current time in milliseconds since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn unix_millis() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32) -> wasm_bindgen::JsValue;
+}
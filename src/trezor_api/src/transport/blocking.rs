@@ -0,0 +1,206 @@
+//! # Blocking I/O
+//!
+//! libusb and the standard sockets only offer blocking calls. The native links run
+//! them on a thread of their own, so waiting for the device suspends the futures
+//! instead of blocking the executor.
+
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use std::cmp;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::Error;
+use crate::time;
+
+/// How long a single blocking read attempt lasts. The I/O thread runs the other
+/// operations in between.
+const READ_ATTEMPT_MS: u64 = 200;
+
+type ReadResult = Result<Vec<u8>, Error>;
+
+/// Run a blocking operation on its own thread and wait for it.
+///
+/// Spawns a thread per call, so it is meant for the occasional calls. The links
+/// run their transfers on their [IoThread].
+pub(crate) async fn unblock<T, F>(operation: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    let (result_tx, result_rx) = oneshot::channel();
+    thread::spawn(move || {
+        result_tx.send(operation()).ok();
+    });
+    result_rx
+        .await
+        .map_err(|_| Error::Internal("the I/O thread panicked".to_owned()))?
+}
+
+enum Request {
+    /// Run an operation.
+    Run(Box<dyn FnOnce() + Send>),
+    /// Read a chunk until the deadline.
+    Read(Instant, oneshot::Sender<ReadResult>),
+}
+
+/// The thread a link runs its blocking transfers on.
+///
+/// The chunk reads outlive the futures waiting on them, so when a wait is abandoned,
+/// f.e. after a timeout or a cancellation, the chunk goes to the next read instead of
+/// being lost. While a read is running, the other operations run in between the read
+/// attempts. The thread stops once the link is dropped.
+pub(crate) struct IoThread {
+    request_tx: mpsc::Sender<Request>,
+    /// The result of the running read.
+    result_rx: Option<oneshot::Receiver<ReadResult>>,
+}
+
+impl IoThread {
+    /// Start the thread.
+    ///
+    /// `attempt` tries to read a chunk within the given duration and returns `None`
+    /// if nothing arrived.
+    pub(crate) fn spawn<F>(attempt: F) -> Result<IoThread, Error>
+    where
+        F: FnMut(Duration) -> Result<Option<Vec<u8>>, Error> + Send + 'static,
+    {
+        let (request_tx, request_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("trezor-io".to_owned())
+            .spawn(move || serve(request_rx, attempt))
+            .map_err(|e| Error::Internal(format!("failed to start the I/O thread: {}", e)))?;
+        Ok(IoThread {
+            request_tx,
+            result_rx: None,
+        })
+    }
+
+    /// Run a blocking operation on the thread and wait for it.
+    pub(crate) async fn run<T, F>(&self, operation: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> Result<T, Error> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+        let operation = Box::new(move || {
+            result_tx.send(operation()).ok();
+        });
+        self.request_tx
+            .send(Request::Run(operation))
+            .map_err(|_| Error::Internal("the I/O thread stopped".to_owned()))?;
+        result_rx
+            .await
+            .map_err(|_| Error::Internal("the I/O thread panicked".to_owned()))?
+    }
+
+    /// Wait for the next chunk, starting a read if none is running.
+    ///
+    /// The read attempts are retried until `timeout`, then the read fails with
+    /// [Error::DeviceReadTimeout].
+    pub(crate) async fn read(&mut self, timeout: Duration) -> ReadResult {
+        if self.result_rx.is_none() {
+            let (result_tx, result_rx) = oneshot::channel();
+            self.request_tx
+                .send(Request::Read(Instant::now() + timeout, result_tx))
+                .map_err(|_| Error::Internal("the I/O thread stopped".to_owned()))?;
+            self.result_rx = Some(result_rx);
+        }
+        let res = self.result_rx.as_mut().expect("a read is running").await;
+        self.result_rx = None;
+        res.map_err(|_| Error::Internal("the I/O thread panicked".to_owned()))?
+    }
+
+    /// Wait up to `wait` for the next chunk, leaving the read running if nothing arrives.
+    pub(crate) async fn read_timeout(
+        &mut self,
+        timeout: Duration,
+        wait: Duration,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let read = Box::pin(self.read(timeout));
+        match future::select(read, Box::pin(time::sleep(wait))).await {
+            Either::Left((Err(Error::DeviceReadTimeout), _)) | Either::Right(_) => Ok(None),
+            Either::Left((res, _)) => res.map(Some),
+        }
+    }
+}
+
+/// The loop of the I/O thread.
+fn serve<F>(requests: mpsc::Receiver<Request>, mut attempt: F)
+where
+    F: FnMut(Duration) -> Result<Option<Vec<u8>>, Error>,
+{
+    let mut read: Option<(Instant, oneshot::Sender<ReadResult>)> = None;
+    loop {
+        // Only wait for a request when no read is running.
+        let request = match read {
+            Some(_) => match requests.try_recv() {
+                Ok(request) => Some(request),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            },
+            None => match requests.recv() {
+                Ok(request) => Some(request),
+                Err(_) => return,
+            },
+        };
+        match request {
+            Some(Request::Run(operation)) => operation(),
+            Some(Request::Read(deadline, result_tx)) => read = Some((deadline, result_tx)),
+            None => {
+                let (deadline, result_tx) = read.take().expect("a read is running");
+                let now = Instant::now();
+                if now >= deadline {
+                    result_tx.send(Err(Error::DeviceReadTimeout)).ok();
+                    continue;
+                }
+                let attempt_timeout =
+                    cmp::min(Duration::from_millis(READ_ATTEMPT_MS), deadline - now);
+                match attempt(attempt_timeout) {
+                    Ok(Some(chunk)) => {
+                        result_tx.send(Ok(chunk)).ok();
+                    }
+                    Ok(None) => read = Some((deadline, result_tx)),
+                    Err(e) => {
+                        result_tx.send(Err(e)).ok();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_abandoned_read_is_kept() {
+        let (chunk_tx, chunk_rx) = mpsc::channel::<Vec<u8>>();
+        let mut io = IoThread::spawn(move |wait| Ok(chunk_rx.recv_timeout(wait).ok())).unwrap();
+        let timeout = Duration::from_secs(10);
+
+        let res = block_on(io.read_timeout(timeout, Duration::from_millis(10)));
+        assert!(matches!(res, Ok(None)));
+
+        // The operations run while the read is waiting.
+        assert_eq!(block_on(io.run(|| Ok(1))).unwrap(), 1);
+
+        // The chunk arrives after the wait was abandoned.
+        chunk_tx.send(vec![1]).unwrap();
+        assert_eq!(block_on(io.read(timeout)).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_read_timeout() {
+        let mut io = IoThread::spawn(|wait| {
+            thread::sleep(wait);
+            Ok(None)
+        })
+        .unwrap();
+        let res = block_on(io.read(Duration::from_millis(10)));
+        assert!(matches!(res, Err(Error::DeviceReadTimeout)));
+    }
+}
@@ -0,0 +1,467 @@
+//! # Trezor Bridge transport
+//!
+//! Talks to the devices through the HTTP API of Trezor Bridge (trezord), so the
+//! devices can be shared with the other applications using the Bridge.
+
+use async_trait::async_trait;
+use byteorder::{BigEndian, ByteOrder};
+use futures::future::BoxFuture;
+use protobuf::ProtobufEnum;
+use std::fmt;
+
+use super::{AvailableDeviceTransport, Error, ProtoMessage, Transport};
+use crate::protos::MessageType;
+use crate::{AvailableDevice, DeviceMode, TrezorModel};
+
+/// The address Trezor Bridge listens on.
+pub const BRIDGE_URL: &str = "http://127.0.0.1:21325";
+
+/// The origin the native requests identify with by default, the one of the Python
+/// Trezor client.
+pub const DEFAULT_BRIDGE_ORIGIN: &str = "https://python.trezor.io";
+
+/// How to reach Trezor Bridge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BridgeConfig {
+    /// The URL of the Bridge, [BRIDGE_URL] by default.
+    pub url: String,
+    /// The `Origin` header of the native requests, [DEFAULT_BRIDGE_ORIGIN] by default.
+    ///
+    /// The Bridge only accepts requests from the origins it whitelists, so applications
+    /// with an origin of their own whitelisted should set it here. Browsers set the
+    /// origin of the page themselves, so it is ignored on wasm.
+    pub origin: String,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        BridgeConfig {
+            url: BRIDGE_URL.to_owned(),
+            origin: DEFAULT_BRIDGE_ORIGIN.to_owned(),
+        }
+    }
+}
+
+/// A device as listed by the `enumerate` call.
+#[derive(Deserialize, Debug)]
+struct BridgeDevice {
+    path: String,
+    session: Option<String>,
+    vendor: u16,
+    product: u16,
+    #[serde(default)]
+    debug: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct BridgeSession {
+    session: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct BridgeError {
+    error: String,
+}
+
+/// An available transport for connecting with a device.
+#[derive(Debug)]
+pub struct AvailableBridgeTransport {
+    /// The URL of the Bridge.
+    pub url: String,
+    /// The origin the requests identify with, see [BridgeConfig::origin].
+    pub origin: String,
+    /// The path of the device, as assigned by the Bridge.
+    pub path: String,
+    /// The session another client holds on the device, if any.
+    pub session: Option<String>,
+}
+
+impl fmt::Display for AvailableBridgeTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bridge ({}/{})", self.url, self.path)
+    }
+}
+
+/// An implementation of the Transport interface for devices behind Trezor Bridge.
+pub struct BridgeTransport {
+    url: String,
+    origin: String,
+    path: String,
+    /// The session this transport holds, or the one to steal on acquire.
+    previous_session: Option<String>,
+    session: Option<String>,
+    /// The `/read` in flight. It is kept when a read is abandoned, f.e. on a
+    /// cancellation, so the message goes to the next read instead of being lost.
+    pending_read: Option<BoxFuture<'static, Result<String, Error>>>,
+}
+
+impl BridgeTransport {
+    /// Find devices through the Bridge running at [BRIDGE_URL].
+    pub async fn find_devices() -> Result<Vec<AvailableDevice>, Error> {
+        Self::find_devices_with(&BridgeConfig::default()).await
+    }
+
+    /// Find devices through the Bridge running at the given URL.
+    pub async fn find_devices_at(url: &str) -> Result<Vec<AvailableDevice>, Error> {
+        let config = BridgeConfig {
+            url: url.to_owned(),
+            ..Default::default()
+        };
+        Self::find_devices_with(&config).await
+    }
+
+    /// Find devices through the Bridge with the given configuration.
+    pub async fn find_devices_with(config: &BridgeConfig) -> Result<Vec<AvailableDevice>, Error> {
+        let url = &config.url;
+        let resp = post(format!("{}/enumerate", url), config.origin.clone(), None).await?;
+        let devices: Vec<BridgeDevice> = parse_json(&resp)?;
+        log::trace!("found {} devices through the Bridge at {}", devices.len(), url);
+
+        Ok(devices
+            .into_iter()
+            .filter_map(|device| {
+                let model = TrezorModel::from_usb_ids(device.vendor, device.product)?;
+                Some(AvailableDevice {
+                    model,
+                    mode: DeviceMode::from_usb_ids(device.vendor, device.product),
+                    debug: device.debug,
+                    transport: AvailableDeviceTransport::Bridge(AvailableBridgeTransport {
+                        url: url.to_owned(),
+                        origin: config.origin.clone(),
+                        path: device.path,
+                        session: device.session,
+                    }),
+                })
+            })
+            .collect())
+    }
+
+    /// Connect to a device through the Bridge.
+    pub fn connect(device: &AvailableDevice) -> Result<Box<dyn Transport>, Error> {
+        let transport = match device.transport {
+            AvailableDeviceTransport::Bridge(ref t) => t,
+            _ => panic!("passed wrong AvailableDevice in BridgeTransport::connect"),
+        };
+        log::debug!("connecting to {}", device);
+
+        Ok(Box::new(BridgeTransport {
+            url: transport.url.clone(),
+            origin: transport.origin.clone(),
+            path: transport.path.clone(),
+            previous_session: transport.session.clone(),
+            session: None,
+            pending_read: None,
+        }))
+    }
+
+    fn session(&self) -> Result<&str, Error> {
+        self.session.as_deref().ok_or(Error::NoSession)
+    }
+}
+
+#[async_trait]
+impl Transport for BridgeTransport {
+    async fn session_begin(&mut self) -> Result<(), Error> {
+        if self.session.is_some() {
+            return Ok(());
+        }
+        let previous = self.previous_session.as_deref().unwrap_or("null");
+        let url = format!("{}/acquire/{}/{}", self.url, self.path, previous);
+        let resp: BridgeSession = parse_json(&post(url, self.origin.clone(), None).await?)?;
+
+        self.previous_session = Some(resp.session.clone());
+        self.session = Some(resp.session);
+        Ok(())
+    }
+
+    async fn session_end(&mut self) -> Result<(), Error> {
+        self.pending_read = None;
+        if let Some(session) = self.session.take() {
+            let url = format!("{}/release/{}", self.url, session);
+            post(url, self.origin.clone(), None).await?;
+            self.previous_session = None;
+        }
+        Ok(())
+    }
+
+    async fn write_message(&mut self, message: ProtoMessage) -> Result<(), Error> {
+        let url = format!("{}/post/{}", self.url, self.session()?);
+        post(url, self.origin.clone(), Some(encode_message(&message))).await?;
+        Ok(())
+    }
+
+    async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
+        if self.pending_read.is_none() {
+            let url = format!("{}/read/{}", self.url, self.session()?);
+            self.pending_read = Some(Box::pin(post(url, self.origin.clone(), None)));
+        }
+        let resp = self.pending_read.as_mut().expect("a pending read").await;
+        self.pending_read = None;
+        decode_message(&resp?)
+    }
+}
+
+/// Encode a message as the hex string the Bridge expects: the message type, the
+/// payload length and the payload.
+fn encode_message(message: &ProtoMessage) -> String {
+    let mut data = vec![0; 6];
+    BigEndian::write_u16(&mut data[0..2], message.message_type().value() as u16);
+    BigEndian::write_u32(&mut data[2..6], message.payload().len() as u32);
+    data.extend(message.payload());
+    hex::encode(data)
+}
+
+fn decode_message(resp: &str) -> Result<ProtoMessage, Error> {
+    let data = hex::decode(resp.trim())
+        .map_err(|e| Error::Bridge(format!("invalid message from the Bridge: {}", e)))?;
+    if data.len() < 6 {
+        return Err(Error::Bridge(
+            "truncated message from the Bridge".to_owned(),
+        ));
+    }
+    let message_type_id = BigEndian::read_u16(&data[0..2]) as u32;
+    let message_type = MessageType::from_i32(message_type_id as i32)
+        .ok_or(Error::InvalidMessageType(message_type_id))?;
+    let length = BigEndian::read_u32(&data[2..6]) as usize;
+    if data.len() < 6 + length {
+        return Err(Error::Bridge(
+            "truncated message from the Bridge".to_owned(),
+        ));
+    }
+    Ok(ProtoMessage(message_type, data[6..6 + length].to_vec()))
+}
+
+fn parse_json<'a, T: serde::Deserialize<'a>>(resp: &'a str) -> Result<T, Error> {
+    serde_json::from_str(resp)
+        .map_err(|e| Error::Bridge(format!("invalid response from the Bridge: {}", e)))
+}
+
+/// Turn the body of a failed request into an error.
+fn bridge_error(resp: &str) -> Error {
+    match serde_json::from_str::<BridgeError>(resp) {
+        Ok(BridgeError { error }) if error == "device not found" => Error::DeviceNotFound,
+        Ok(BridgeError { error }) if error.contains("disconnected") => Error::DeviceDisconnected,
+        Ok(BridgeError { error }) => Error::Bridge(error),
+        Err(_) => Error::Bridge(resp.to_owned()),
+    }
+}
+
+/// Send a POST request to the Bridge and get the body of the response.
+///
+/// ureq blocks until the Bridge answers, which takes as long as the user does on
+/// `/read`, so the request runs on its own thread.
+#[cfg(not(target_arch = "wasm32"))]
+async fn post(url: String, origin: String, body: Option<String>) -> Result<String, Error> {
+    super::blocking::unblock(move || post_blocking(&url, &origin, body)).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn post_blocking(url: &str, origin: &str, body: Option<String>) -> Result<String, Error> {
+    let req = ureq::post(url).set("Origin", origin);
+    let resp = match body {
+        Some(body) => req.send_string(&body),
+        None => req.call(),
+    };
+    match resp {
+        Ok(resp) => resp.into_string().map_err(|e| Error::Bridge(e.to_string())),
+        Err(ureq::Error::Status(_, resp)) => {
+            Err(bridge_error(&resp.into_string().unwrap_or_default()))
+        }
+        Err(e) => Err(Error::Bridge(e.to_string())),
+    }
+}
+
+/// Send a POST request to the Bridge and get the body of the response.
+///
+/// The JS futures can't be sent between threads, so the request is made on a
+/// local task and only the result is passed back.
+#[cfg(target_arch = "wasm32")]
+async fn post(url: String, _origin: String, body: Option<String>) -> Result<String, Error> {
+    let (result_tx, result_rx) = futures::channel::oneshot::channel();
+    wasm_bindgen_futures::spawn_local(async move {
+        result_tx.send(fetch(url, body).await).ok();
+    });
+    result_rx
+        .await
+        .map_err(|e| Error::Internal(format!("Error receiving result: {}", e)))?
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch(url: String, body: Option<String>) -> Result<String, Error> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Request, RequestInit, Response};
+
+    let js_error = |e: JsValue| Error::Bridge(format!("{:?}", e));
+
+    let mut init = RequestInit::new();
+    init.method("POST");
+    if let Some(body) = body {
+        init.body(Some(&JsValue::from_str(&body)));
+    }
+    let request = Request::new_with_str_and_init(&url, &init).map_err(js_error)?;
+
+    let window = web_sys::window().ok_or_else(|| Error::Bridge("no window".to_owned()))?;
+    let resp: Response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(js_error)?;
+    let text = JsFuture::from(resp.text().map_err(js_error)?)
+        .await
+        .map_err(js_error)?
+        .as_string()
+        .unwrap_or_default();
+
+    if resp.ok() {
+        Ok(text)
+    } else {
+        Err(bridge_error(&text))
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::future::{self, Either};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    /// A request to the fake Bridge: the path, the origin and the body.
+    type Request = (String, String, String);
+
+    /// Serve the given responses to the next requests, returning the requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_owned();
+
+                let mut content_length = 0;
+                let mut origin = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    let line = line.to_ascii_lowercase();
+                    if let Some(value) = line.strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if let Some(value) = line.strip_prefix("origin:") {
+                        origin = value.trim().to_owned();
+                    }
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                requests.push((path, origin, String::from_utf8(request_body).unwrap()));
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        let message = ProtoMessage(MessageType::MessageType_Ping, vec![0x0a, 0x01, 0x61]);
+        let encoded = encode_message(&message);
+        assert_eq!(encoded, "0001000000030a0161");
+        assert_eq!(decode_message(&encoded).unwrap(), message);
+        assert!(decode_message("0001000000030a").is_err());
+    }
+
+    #[test]
+    fn test_bridge_session() {
+        let (url, server) = serve(vec![
+            (
+                200,
+                r#"[{"path":"1","session":null,"vendor":4617,"product":21441,"debug":false}]"#,
+            ),
+            (200, r#"{"session":"7"}"#),
+            (200, ""),
+            (200, "0002000000030a0161"),
+            (200, ""),
+        ]);
+
+        let config = BridgeConfig {
+            url,
+            origin: "http://localhost:8000".to_owned(),
+        };
+        let devices = block_on(BridgeTransport::find_devices_with(&config)).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].model, TrezorModel::T);
+
+        let mut transport = BridgeTransport::connect(&devices[0]).unwrap();
+        block_on(async {
+            transport.session_begin().await?;
+            transport
+                .write_message(ProtoMessage(MessageType::MessageType_Ping, vec![]))
+                .await?;
+            let resp = transport.read_message().await?;
+            assert_eq!(resp.message_type(), MessageType::MessageType_Success);
+            transport.session_end().await
+        })
+        .unwrap();
+
+        let requests = server.join().unwrap();
+        let paths: Vec<&str> = requests.iter().map(|(path, _, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/enumerate",
+                "/acquire/1/null",
+                "/post/7",
+                "/read/7",
+                "/release/7"
+            ]
+        );
+        assert_eq!(requests[2].2, "000100000000");
+        // The transports of the devices found keep the origin.
+        assert!(requests
+            .iter()
+            .all(|(_, origin, _)| origin == "http://localhost:8000"));
+    }
+
+    #[test]
+    fn test_post_does_not_block() {
+        // A Bridge waiting for the user never answers the read.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/read/7", listener.local_addr().unwrap());
+
+        let read = Box::pin(post(url, DEFAULT_BRIDGE_ORIGIN.to_owned(), None));
+        let sleep = Box::pin(crate::time::sleep(Duration::from_millis(50)));
+        let res = block_on(future::select(read, sleep));
+        assert!(matches!(res, Either::Right(_)));
+    }
+
+    #[test]
+    fn test_bridge_error() {
+        let (url, server) = serve(vec![(400, r#"{"error":"device not found"}"#)]);
+        let url = format!("{}/acquire/1/null", url);
+        let res = block_on(post(url, DEFAULT_BRIDGE_ORIGIN.to_owned(), None));
+        assert!(matches!(res, Err(Error::DeviceNotFound)));
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].1, DEFAULT_BRIDGE_ORIGIN);
+    }
+}
@@ -1,5 +1,7 @@
 //! # Error Handling
 
+use std::{error, fmt};
+
 /// Trezor error.
 #[derive(Debug)]
 pub enum Error {
@@ -7,8 +9,14 @@ pub enum Error {
     Usb(String),
     /// Error from `webusb` mod.
     WebUsb(String),
+    /// Error from hidapi.
+    Hid(String),
     /// Error from Udp
     Udp(std::io::Error),
+    /// Error from Trezor Bridge.
+    Bridge(String),
+    /// Error writing or reading a capture of the traffic.
+    Capture(String),
     /// The device to connect to was not found.
     DeviceNotFound,
     /// The device is no longer available.
@@ -23,6 +31,8 @@ pub enum Error {
     DeviceBadMagic,
     /// The device sent a message with a wrong session id.
     DeviceBadSessionId,
+    /// A message was exchanged outside of a protocol session.
+    NoSession,
     /// The device sent an unexpected sequence number.
     DeviceUnexpectedSequenceNumber,
     /// Received a non-existing message type from the device.
@@ -40,3 +50,49 @@ impl From<rusb::Error> for Error {
     }
 }
 
+#[cfg(all(feature = "hid", not(target_arch = "wasm32")))]
+impl From<hidapi::HidError> for Error {
+    fn from(e: hidapi::HidError) -> Error {
+        Error::Hid(e.to_string())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usb(e) => write!(f, "USB error: {}", e),
+            Error::WebUsb(e) => write!(f, "WebUSB error: {}", e),
+            Error::Hid(e) => write!(f, "HID error: {}", e),
+            Error::Udp(e) => write!(f, "UDP error: {}", e),
+            Error::Bridge(e) => write!(f, "Trezor Bridge error: {}", e),
+            Error::Capture(e) => write!(f, "capture error: {}", e),
+            Error::DeviceNotFound => write!(f, "the device was not found"),
+            Error::DeviceDisconnected => write!(f, "the device was disconnected"),
+            Error::UnknownHidVersion => write!(f, "unknown HID version"),
+            Error::UnexpectedChunkSizeFromDevice(size) => {
+                write!(f, "the device sent a chunk of unexpected size: {}", size)
+            }
+            Error::DeviceReadTimeout => write!(f, "timeout reading from the device"),
+            Error::DeviceBadMagic => write!(f, "the device sent a chunk with a bad magic"),
+            Error::DeviceBadSessionId => write!(f, "the device sent a wrong session id"),
+            Error::NoSession => write!(f, "no protocol session"),
+            Error::DeviceUnexpectedSequenceNumber => {
+                write!(f, "the device sent an unexpected sequence number")
+            }
+            Error::InvalidMessageType(t) => {
+                write!(f, "the device sent an invalid message type: {}", t)
+            }
+            Error::NoDeviceSerial => write!(f, "unable to determine the device serial number"),
+            Error::Internal(e) => write!(f, "internal error: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Udp(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! # HID transport
+//!
+//! Trezor Model One devices on firmware older than 1.7 only expose a HID interface,
+//! which the USB transport can't claim. They speak the original binary protocol
+//! in 64 byte HID reports.
+
+use async_trait::async_trait;
+use hidapi::{HidApi, HidDevice};
+use std::cmp;
+use std::ffi::CString;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::blocking::IoThread;
+use super::{
+    AvailableDeviceTransport, Error, Link, ProtoMessage, Protocol, ProtocolV1, Transport,
+    TransportConfig,
+};
+use crate::{AvailableDevice, DeviceMode, TrezorModel, DEV_TREZOR_ONE};
+
+/// The chunk size for the serial protocol.
+const CHUNK_SIZE: usize = 64;
+
+pub const INTERFACE: i32 = 0;
+pub const INTERFACE_DEBUG: i32 = 1;
+
+/// The vendor usage pages of the interfaces.
+pub const USAGE_PAGE: u16 = 0xff00;
+pub const USAGE_PAGE_DEBUG: u16 = 0xff01;
+
+/// An available transport for connecting with a device.
+#[derive(Debug)]
+pub struct AvailableHidTransport {
+    /// The platform-specific path of the HID device.
+    pub path: CString,
+}
+
+impl fmt::Display for AvailableHidTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HID ({})", self.path.to_string_lossy())
+    }
+}
+
+/// A link to a device over HID reports.
+///
+/// The reports are read and written on the I/O thread of the link, which shares the device.
+pub struct HidLink {
+    device: Arc<Mutex<HidDevice>>,
+    /// The HID version, 2 if the writes are prefixed with the report ID.
+    hid_version: u8,
+    io: IoThread,
+    read_timeout: Duration,
+}
+
+fn lock(device: &Mutex<HidDevice>) -> Result<std::sync::MutexGuard<'_, HidDevice>, Error> {
+    device
+        .lock()
+        .map_err(|_| Error::Internal("the HID device lock is poisoned".to_owned()))
+}
+
+impl HidLink {
+    /// Find out whether the platform expects the report ID in front of the written reports.
+    ///
+    /// The device ignores the reports not starting with `?##`, so the probe doesn't
+    /// disturb the protocol.
+    fn probe_hid_version(device: &HidDevice) -> Result<u8, Error> {
+        let mut chunk = vec![0xff; CHUNK_SIZE];
+        chunk[0] = 0x3f;
+        for hid_version in [2, 1].iter().copied() {
+            let report = frame_chunk(hid_version, chunk.clone());
+            if device.write(&report).ok() == Some(report.len()) {
+                return Ok(hid_version);
+            }
+        }
+        Err(Error::UnknownHidVersion)
+    }
+}
+
+/// Turn a chunk into the report written for the HID version.
+fn frame_chunk(hid_version: u8, chunk: Vec<u8>) -> Vec<u8> {
+    match hid_version {
+        // The single report of the device has the ID 0.
+        2 => [&[0x00], &chunk[..]].concat(),
+        _ => chunk,
+    }
+}
+
+/// Turn the bytes of a read report into a chunk, `None` if nothing was read.
+///
+/// The reads carry no report ID, since the device has a single report.
+fn unframe_report(report: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+    match report.len() {
+        0 => Ok(None),
+        CHUNK_SIZE => Ok(Some(report.to_vec())),
+        n => Err(Error::UnexpectedChunkSizeFromDevice(n)),
+    }
+}
+
+/// A read attempt for the I/O thread.
+fn read_attempt(
+    device: Arc<Mutex<HidDevice>>,
+) -> impl FnMut(Duration) -> Result<Option<Vec<u8>>, Error> + Send + 'static {
+    move |timeout| {
+        // One byte more than a chunk, so that oversized reports are noticed.
+        let mut report = vec![0; CHUNK_SIZE + 1];
+        // hidapi takes the timeout in milliseconds as an `i32`, negative meaning forever.
+        let timeout = cmp::min(timeout.as_millis(), i32::MAX as u128) as i32;
+        let n = lock(&device)?.read_timeout(&mut report, timeout)?;
+        unframe_report(&report[..n])
+    }
+}
+
+#[async_trait]
+impl Link for HidLink {
+    async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
+        debug_assert_eq!(CHUNK_SIZE, chunk.len());
+        let report = frame_chunk(self.hid_version, chunk);
+        let device = self.device.clone();
+        self.io
+            .run(move || {
+                lock(&device)?.write(&report)?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
+        self.io.read(self.read_timeout).await
+    }
+
+    async fn read_chunk_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<u8>>, Error> {
+        self.io.read_timeout(self.read_timeout, timeout).await
+    }
+}
+
+/// An implementation of the Transport interface for HID devices.
+pub struct HidTransport {
+    protocol: ProtocolV1<HidLink>,
+}
+
+impl HidTransport {
+    pub fn find_devices() -> Result<Vec<AvailableDevice>, Error> {
+        let api = HidApi::new()?;
+
+        let mut devices = Vec::new();
+        for info in api.device_list() {
+            // Only Trezor One had firmwares without WebUSB.
+            if (info.vendor_id(), info.product_id()) != DEV_TREZOR_ONE {
+                continue;
+            }
+            // The usage page is 0 where the platform doesn't report it.
+            let debug = match (info.usage_page(), info.interface_number()) {
+                (USAGE_PAGE, _) | (0, INTERFACE) => false,
+                (USAGE_PAGE_DEBUG, _) | (0, INTERFACE_DEBUG) => true,
+                // The U2F interface.
+                _ => continue,
+            };
+
+            devices.push(AvailableDevice {
+                model: TrezorModel::One,
+                mode: DeviceMode::Normal,
+                debug,
+                transport: AvailableDeviceTransport::Hid(AvailableHidTransport {
+                    path: info.path().to_owned(),
+                }),
+            });
+        }
+        log::trace!("found {} devices over HID", devices.len());
+        Ok(devices)
+    }
+
+    /// Connect to a device over the HID transport.
+    pub fn connect(device: &AvailableDevice) -> Result<Box<dyn Transport>, Error> {
+        let transport = match device.transport {
+            AvailableDeviceTransport::Hid(ref t) => t,
+            _ => panic!("passed wrong AvailableDevice in HidTransport::connect"),
+        };
+        log::debug!("connecting to {}", device);
+
+        let api = HidApi::new()?;
+        let device = api
+            .open_path(&transport.path)
+            .map_err(|_| Error::DeviceDisconnected)?;
+        let hid_version = HidLink::probe_hid_version(&device)?;
+
+        let device = Arc::new(Mutex::new(device));
+
+        // Legacy firmwares predate the protocol negotiation.
+        Ok(Box::new(HidTransport {
+            protocol: ProtocolV1::new(HidLink {
+                io: IoThread::spawn(read_attempt(device.clone()))?,
+                device,
+                hid_version,
+                read_timeout: TransportConfig::default().read_timeout(),
+            }),
+        }))
+    }
+}
+
+#[async_trait]
+impl super::Transport for HidTransport {
+    async fn session_begin(&mut self) -> Result<(), Error> {
+        self.protocol.session_begin().await
+    }
+    async fn session_end(&mut self) -> Result<(), Error> {
+        self.protocol.session_end().await
+    }
+
+    async fn write_message(&mut self, message: ProtoMessage) -> Result<(), Error> {
+        self.protocol.write(message).await
+    }
+    async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
+        self.protocol.read().await
+    }
+
+    fn set_config(&mut self, config: &TransportConfig) {
+        self.protocol.link.read_timeout = config.read_timeout();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk() -> Vec<u8> {
+        (0..CHUNK_SIZE as u8).collect()
+    }
+
+    #[test]
+    fn test_frame_chunk() {
+        assert_eq!(frame_chunk(1, chunk()), chunk());
+
+        let report = frame_chunk(2, chunk());
+        assert_eq!(report.len(), CHUNK_SIZE + 1);
+        assert_eq!(report[0], 0x00);
+        assert_eq!(&report[1..], &chunk()[..]);
+    }
+
+    #[test]
+    fn test_unframe_report() {
+        assert_eq!(unframe_report(&[]).unwrap(), None);
+        assert_eq!(unframe_report(&chunk()).unwrap(), Some(chunk()));
+        // The reads look the same for both HID versions.
+        assert_eq!(
+            unframe_report(&frame_chunk(1, chunk())).unwrap(),
+            Some(chunk())
+        );
+    }
+
+    #[test]
+    fn test_unframe_report_size() {
+        match unframe_report(&chunk()[..10]) {
+            Err(Error::UnexpectedChunkSizeFromDevice(10)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        match unframe_report(&frame_chunk(2, chunk())) {
+            Err(Error::UnexpectedChunkSizeFromDevice(65)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
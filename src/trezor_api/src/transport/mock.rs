@@ -0,0 +1,268 @@
+//! # Mock transport
+//!
+//! A transport that plays back a scripted conversation instead of talking to a device,
+//! so the client can be tested without hardware.
+//!
+//! ```
+//! # use futures::executor::block_on;
+//! # use trezor_api::{protos, trezor_with_transport, TrezorModel};
+//! # use trezor_api::transport::mock::{MockTransport, Script};
+//! let mut pong = protos::Success::new();
+//! pong.set_message("hello".to_owned());
+//!
+//! let mut ping = protos::Ping::new();
+//! ping.set_message("hello".to_owned());
+//!
+//! let script = Script::new().expect(ping).respond(pong);
+//! let transport = MockTransport::new(script.clone());
+//! let mut trezor = trezor_with_transport(TrezorModel::T, Box::new(transport));
+//!
+//! block_on(async { trezor.ping("hello").await?.ok() }).unwrap();
+//! assert!(script.is_finished());
+//! ```
+
+use async_trait::async_trait;
+use byteorder::{BigEndian, ByteOrder};
+use protobuf::ProtobufEnum;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use super::{v1_chunks, Error, Link, ProtoMessage, Protocol, ProtocolV1, Transport};
+use crate::messages::TrezorMessage;
+use crate::protos::MessageType;
+
+/// The chunk size for the serial protocol.
+const CHUNK_SIZE: usize = 64;
+
+/// A step of a scripted conversation.
+#[derive(Clone, Debug)]
+enum Step {
+    /// The host is expected to send exactly this message.
+    Expect(ProtoMessage),
+    /// The host is expected to send a message of this type with any payload.
+    ExpectType(MessageType),
+    /// The device sends this message.
+    Respond(ProtoMessage),
+}
+
+/// A scripted conversation between the host and the device.
+///
+/// Every message the host sends is checked against the next expected one, then all
+/// the responses up to the next expectation are sent back. Clones share the script,
+/// so a clone can be kept to check the conversation after the transport was moved.
+#[derive(Clone, Debug, Default)]
+pub struct Script {
+    steps: Arc<Mutex<VecDeque<Step>>>,
+}
+
+impl Script {
+    pub fn new() -> Script {
+        Script::default()
+    }
+
+    /// Expect the host to send the given message.
+    ///
+    /// Panics if the message misses required fields, use [Script::expect_raw] then.
+    pub fn expect<M: TrezorMessage>(self, message: M) -> Script {
+        let payload = message.write_to_bytes().expect("a valid message");
+        self.expect_raw(ProtoMessage(M::message_type(), payload))
+    }
+
+    /// Expect the host to send the given raw message.
+    pub fn expect_raw(self, message: ProtoMessage) -> Script {
+        self.push(Step::Expect(message))
+    }
+
+    /// Expect the host to send a message of the given type, whatever its payload.
+    pub fn expect_type(self, message_type: MessageType) -> Script {
+        self.push(Step::ExpectType(message_type))
+    }
+
+    /// Respond with the given message.
+    ///
+    /// Panics if the message misses required fields, use [Script::respond_raw] then.
+    pub fn respond<M: TrezorMessage>(self, message: M) -> Script {
+        let payload = message.write_to_bytes().expect("a valid message");
+        self.respond_raw(ProtoMessage(M::message_type(), payload))
+    }
+
+    /// Respond with the given raw message.
+    pub fn respond_raw(self, message: ProtoMessage) -> Script {
+        self.push(Step::Respond(message))
+    }
+
+    /// Whether the whole conversation has been played.
+    pub fn is_finished(&self) -> bool {
+        self.steps.lock().unwrap().is_empty()
+    }
+
+    fn push(self, step: Step) -> Script {
+        self.steps.lock().unwrap().push_back(step);
+        self
+    }
+
+    /// Check the message sent by the host and get the responses to it.
+    fn receive(&self, message: ProtoMessage) -> Result<Vec<ProtoMessage>, Error> {
+        let mut steps = self.steps.lock().unwrap();
+        let matches = match steps.pop_front() {
+            Some(Step::Expect(expected)) => expected == message,
+            Some(Step::ExpectType(expected)) => expected == message.message_type(),
+            Some(Step::Respond(_)) | None => false,
+        };
+        if !matches {
+            return Err(Error::Internal(format!(
+                "unexpected {:?} message sent to the mock device",
+                message.message_type()
+            )));
+        }
+
+        let mut responses = Vec::new();
+        while let Some(Step::Respond(_)) = steps.front() {
+            if let Some(Step::Respond(resp)) = steps.pop_front() {
+                responses.push(resp);
+            }
+        }
+        Ok(responses)
+    }
+}
+
+/// A link to a mock device playing back a [Script] with the original binary protocol.
+pub struct MockLink {
+    script: Script,
+    /// The unprefixed data of the message being written.
+    written: Vec<u8>,
+    to_read: VecDeque<Vec<u8>>,
+}
+
+impl MockLink {
+    pub fn new(script: Script) -> MockLink {
+        MockLink {
+            script,
+            written: Vec::new(),
+            to_read: VecDeque::new(),
+        }
+    }
+
+    /// Parse the written data if a whole message has been written.
+    fn take_written_message(&mut self) -> Result<Option<ProtoMessage>, Error> {
+        if self.written.len() < 8 {
+            return Ok(None);
+        }
+        if self.written[0..2] != [0x23, 0x23] {
+            return Err(Error::Internal(
+                "bad magic sent to the mock device".to_owned(),
+            ));
+        }
+        let data_length = BigEndian::read_u32(&self.written[4..8]) as usize;
+        if self.written.len() < 8 + data_length {
+            return Ok(None);
+        }
+
+        let message_type_id = BigEndian::read_u16(&self.written[2..4]) as u32;
+        let message_type = MessageType::from_i32(message_type_id as i32)
+            .ok_or(Error::InvalidMessageType(message_type_id))?;
+        let payload = self.written[8..8 + data_length].to_vec();
+        // The rest of the last chunk is padding.
+        self.written.clear();
+        Ok(Some(ProtoMessage(message_type, payload)))
+    }
+}
+
+#[async_trait]
+impl Link for MockLink {
+    async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
+        if chunk.len() != CHUNK_SIZE {
+            return Err(Error::UnexpectedChunkSizeFromDevice(chunk.len()));
+        }
+        if chunk[0] != 0x3f {
+            return Err(Error::Internal(
+                "bad magic sent to the mock device".to_owned(),
+            ));
+        }
+        self.written.extend(&chunk[1..]);
+
+        if let Some(message) = self.take_written_message()? {
+            for resp in self.script.receive(message)? {
+                self.to_read.extend(v1_chunks(resp));
+            }
+        }
+        Ok(())
+    }
+
+    async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
+        self.to_read
+            .pop_front()
+            .ok_or_else(|| Error::Internal("the mock device has nothing to send".to_owned()))
+    }
+}
+
+/// An implementation of the Transport interface playing back a [Script].
+pub struct MockTransport {
+    protocol: ProtocolV1<MockLink>,
+}
+
+impl MockTransport {
+    pub fn new(script: Script) -> MockTransport {
+        MockTransport {
+            protocol: ProtocolV1::new(MockLink::new(script)),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn session_begin(&mut self) -> Result<(), Error> {
+        self.protocol.session_begin().await
+    }
+    async fn session_end(&mut self) -> Result<(), Error> {
+        self.protocol.session_end().await
+    }
+
+    async fn write_message(&mut self, message: ProtoMessage) -> Result<(), Error> {
+        self.protocol.write(message).await
+    }
+    async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
+        self.protocol.read().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos;
+    use futures::executor::block_on;
+    use protobuf::Message;
+
+    #[test]
+    fn test_v1_chunking() {
+        // Long enough to span several chunks in both directions.
+        let text = "a".repeat(300);
+        let mut ping = protos::Ping::new();
+        ping.set_message(text.clone());
+        let mut pong = protos::Success::new();
+        pong.set_message(text);
+
+        let script = Script::new().expect(ping.clone()).respond(pong.clone());
+        let mut transport = MockTransport::new(script.clone());
+
+        let payload = ping.write_to_bytes().unwrap();
+        let request = ProtoMessage(MessageType::MessageType_Ping, payload);
+        block_on(transport.write_message(request)).unwrap();
+        assert!(transport.protocol.link.to_read.len() > 1);
+
+        let resp = block_on(transport.read_message()).unwrap();
+        assert_eq!(resp.message_type(), MessageType::MessageType_Success);
+        assert_eq!(resp.into_message::<protos::Success>().unwrap(), pong);
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_unexpected_request() {
+        let script = Script::new().expect_type(MessageType::MessageType_Initialize);
+        let mut transport = MockTransport::new(script);
+
+        let request = ProtoMessage(MessageType::MessageType_Ping, vec![]);
+        let res = block_on(transport.write_message(request));
+        assert!(matches!(res, Err(Error::Internal(_))));
+    }
+}
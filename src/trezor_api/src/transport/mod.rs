@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use std::fmt;
+use std::time::Duration;
 
 use crate::AvailableDevice;
 use crate::protos::MessageType;
@@ -12,6 +13,17 @@ mod protocol;
 
 pub use protocol::*;
 
+pub mod mock;
+
+pub mod recording;
+
+pub mod bridge;
+
+use bridge::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod blocking;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod usb;
 
@@ -21,6 +33,12 @@ use usb::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod udp;
 
+#[cfg(all(feature = "hid", not(target_arch = "wasm32")))]
+pub mod hid;
+
+#[cfg(all(feature = "hid", not(target_arch = "wasm32")))]
+use hid::*;
+
 #[cfg(not(target_arch = "wasm32"))]
 use udp::*;
 
@@ -29,48 +47,160 @@ pub mod webusb;
 
 #[cfg(target_arch = "wasm32")]
 use webusb::*;
-use crate::transport::webusb::WebUsbTransport;
 
 pub const DEV_TREZOR_ONE: (u16, u16) = (0x534C, 0x0001);
 pub const DEV_TREZOR_T: (u16, u16) = (0x1209, 0x53C1);
-// pub const DEV_TREZOR2_BL: (u16, u16) = (0x1209, 0x53C0);
+pub const DEV_TREZOR_T_BL: (u16, u16) = (0x1209, 0x53C0);
 
 /// An available transport for a Trezor device, containing any of the
 /// different supported transports.
 #[derive(Debug)]
 #[cfg(not(target_arch = "wasm32"))]
 pub enum AvailableDeviceTransport {
-    // Hid(hid::AvailableHidTransport),
+    #[cfg(feature = "hid")]
+    Hid(AvailableHidTransport),
     Usb(AvailableUsbTransport),
     Udp(AvailableUdpTransport),
+    Bridge(AvailableBridgeTransport),
 }
 
 #[derive(Debug)]
 #[cfg(target_arch = "wasm32")]
 pub enum AvailableDeviceTransport {
     WebUsb(AvailableWebUsbTransport),
+    Bridge(AvailableBridgeTransport),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AvailableDeviceTransport {
+    /// The USB serial number of the device.
+    ///
+    /// Fails with [Error::NoDeviceSerial] if the device couldn't be opened to read it,
+    /// or if it is not a USB device. Trezor Model T reports only zeros, which is
+    /// treated as no serial number, use `Features::device_id` instead.
+    pub fn serial_number(&self) -> Result<&str, Error> {
+        match self {
+            Self::Usb(ref t) => match t.serial_number {
+                Some(ref serial) if serial.chars().any(|c| c != '0') => Ok(serial),
+                _ => Err(Error::NoDeviceSerial),
+            },
+            _ => Err(Error::NoDeviceSerial),
+        }
+    }
+
+    /// An identity of the device that stays the same across enumerations.
+    ///
+    /// USB devices are identified by their serial number, or by their position on
+    /// the bus without one, which changes when they are plugged in again. That's
+    /// the case of Trezor Model T, see [Self::has_stable_id].
+    pub fn id(&self) -> String {
+        match self {
+            #[cfg(feature = "hid")]
+            Self::Hid(ref t) => format!("hid:{}", t.path.to_string_lossy()),
+            Self::Usb(ref t) => match self.serial_number() {
+                Ok(serial) => serial.to_owned(),
+                Err(_) => format!("usb:{}:{}", t.bus, t.address),
+            },
+            Self::Udp(ref t) => format!("udp:{}", t.address()),
+            Self::Bridge(ref t) => format!("bridge:{}", t.path),
+        }
+    }
+
+    /// Whether [Self::id] stays the same when the device is plugged in again.
+    ///
+    /// Only the USB serial number identifies a device for good, the HID paths, the
+    /// positions on the bus and the Bridge paths are given out again on every plug.
+    /// Read `Features::device_id` to recognize the other devices.
+    pub fn has_stable_id(&self) -> bool {
+        match self {
+            Self::Usb(_) => self.serial_number().is_ok(),
+            Self::Udp(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the device is attached over USB, and listed by [crate::find_devices].
+    pub(crate) fn is_usb(&self) -> bool {
+        match self {
+            #[cfg(feature = "hid")]
+            Self::Hid(_) => true,
+            Self::Usb(_) => true,
+            Self::Udp(_) | Self::Bridge(_) => false,
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl AvailableDeviceTransport {
+    /// The USB serial number of the device.
+    ///
+    /// Fails with [Error::NoDeviceSerial] if the browser didn't report it, or if it
+    /// is not a WebUSB device.
+    pub fn serial_number(&self) -> Result<&str, Error> {
+        match self {
+            // The plugin names the devices without a serial number `bootloader<n>`.
+            Self::WebUsb(ref t) if !t.path().starts_with("bootloader") => Ok(t.path()),
+            _ => Err(Error::NoDeviceSerial),
+        }
+    }
+
+    /// An identity of the device that stays the same across enumerations.
+    ///
+    /// WebUSB devices are identified by their serial number, or by a name the plugin
+    /// gives out again on every plug without one, see [Self::has_stable_id].
+    pub fn id(&self) -> String {
+        match self {
+            Self::WebUsb(ref t) => t.path().to_owned(),
+            Self::Bridge(ref t) => format!("bridge:{}", t.path),
+        }
+    }
+
+    /// Whether [Self::id] stays the same when the device is plugged in again.
+    ///
+    /// Only the USB serial number identifies a device for good. Read
+    /// `Features::device_id` to recognize the other devices.
+    pub fn has_stable_id(&self) -> bool {
+        match self {
+            Self::WebUsb(_) => self.serial_number().is_ok(),
+            Self::Bridge(_) => false,
+        }
+    }
+
+    /// Whether the device is attached over WebUSB, and listed by [crate::find_devices].
+    pub(crate) fn is_usb(&self) -> bool {
+        match self {
+            Self::WebUsb(_) => true,
+            Self::Bridge(_) => false,
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl fmt::Display for AvailableDeviceTransport {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            #[cfg(feature = "hid")]
+            Self::Hid(ref t) => write!(f, "{}", t),
             Self::Usb(ref t) => write!(f, "{}", t),
             Self::Udp(ref t) => write!(f, "{}", t),
+            Self::Bridge(ref t) => write!(f, "{}", t),
         }
     }
 }
 
+#[cfg(target_arch = "wasm32")]
 impl fmt::Display for AvailableDeviceTransport {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::WebUsb(ref t) => write!(f, "{}", t),
+            Self::Bridge(ref t) => write!(f, "{}", t),
         }
     }
 }
 
 /// A protobuf message accompanied by the message type.  This type is used to pass messages over the
 /// transport and used to contain messages received from the transport.
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct ProtoMessage(pub MessageType, pub Vec<u8>);
 
 impl ProtoMessage {
@@ -93,23 +223,61 @@ impl ProtoMessage {
     }
 }
 
+/// How long to wait for the device.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransportConfig {
+    /// How long the device may take to answer a request.
+    pub device_timeout: Duration,
+    /// How long the user may take to confirm a prompt on the device.
+    pub user_timeout: Duration,
+    /// Ask the device for a V2 protocol session before the first message, over the
+    /// transports which support it. Only applies before the device is talked to.
+    pub protocol_v2: bool,
+}
+
+impl TransportConfig {
+    /// The longest a single read may take.
+    pub fn read_timeout(&self) -> Duration {
+        std::cmp::max(self.device_timeout, self.user_timeout)
+    }
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        TransportConfig {
+            device_timeout: Duration::from_secs(30),
+            user_timeout: Duration::from_secs(100),
+            protocol_v2: false,
+        }
+    }
+}
+
 /// The transport interface that is implemented by the different ways to communicate with a Trezor
 /// device.
+///
+/// Transports are `Send`, so a connected [Trezor](crate::Trezor) can be moved to another thread
+/// or task.
 #[async_trait]
-pub trait Transport {
+pub trait Transport: Send {
     async fn session_begin(&mut self) -> Result<(), error::Error>;
     async fn session_end(&mut self) -> Result<(), error::Error>;
 
     async fn write_message(&mut self, message: ProtoMessage) -> Result<(), error::Error>;
     async fn read_message(&mut self) -> Result<ProtoMessage, error::Error>;
+
+    /// Apply the timeouts of the configuration. Transports without timeouts of their own ignore it.
+    fn set_config(&mut self, _config: &TransportConfig) {}
 }
 
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn connect(device: &AvailableDevice) -> Result<Box<dyn Transport>, Error> {
     match &device.transport {
+        #[cfg(feature = "hid")]
+        AvailableDeviceTransport::Hid(_) => HidTransport::connect(device),
         AvailableDeviceTransport::Usb(_) => UsbTransport::connect(device),
         AvailableDeviceTransport::Udp(_) => UdpTransport::connect(device),
+        AvailableDeviceTransport::Bridge(_) => BridgeTransport::connect(device),
     }
 }
 
@@ -117,5 +285,6 @@ pub fn connect(device: &AvailableDevice) -> Result<Box<dyn Transport>, Error> {
 pub fn connect(device: &AvailableDevice) -> Result<Box<dyn Transport>, Error> {
     match &device.transport {
         AvailableDeviceTransport::WebUsb(_) => WebUsbTransport::connect(device),
+        AvailableDeviceTransport::Bridge(_) => BridgeTransport::connect(device),
     }
 }
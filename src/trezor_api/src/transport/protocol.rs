@@ -1,17 +1,49 @@
 use async_trait::async_trait;
 use std::cmp;
+use std::time::Duration;
 
 use byteorder::{BigEndian, ByteOrder};
+use futures::future::{self, Either};
+use log::{debug, trace, warn};
 use protobuf::ProtobufEnum;
 
 use super::{Error, ProtoMessage};
 use crate::protos::MessageType;
+use crate::time;
 
 /// A link represents a serial connection to send and receive byte chunks from and to a device.
 #[async_trait]
 pub trait Link {
     async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error>;
     async fn read_chunk(&mut self) -> Result<Vec<u8>, Error>;
+
+    /// Read a chunk, or `None` if the device didn't send anything within `timeout`.
+    async fn read_chunk_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<u8>>, Error>
+    where
+        Self: Send,
+    {
+        let read = self.read_chunk();
+        let sleep = Box::pin(time::sleep(timeout));
+        match future::select(read, sleep).await {
+            Either::Left((chunk, _)) => chunk.map(Some),
+            Either::Right(_) => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl<L: Link + Send> Link for &mut L {
+    async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
+        (**self).write_chunk(chunk).await
+    }
+
+    async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
+        (**self).read_chunk().await
+    }
+
+    async fn read_chunk_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<u8>>, Error> {
+        (**self).read_chunk_timeout(timeout).await
+    }
 }
 
 /// A protocol is used to encode messages in chunks that can be sent to the device and to parse
@@ -27,49 +59,172 @@ pub trait Protocol {
 /// The length of the chunks sent.
 const REPLEN: usize = 64;
 
+/// How long to wait for a V2 session while negotiating the protocol version.
+pub const NEGOTIATION_TIMEOUT_MS: u64 = 250;
+
+const V2_MAGIC_FIRST_CHUNK: u8 = 0x01;
+const V2_MAGIC_NEXT_CHUNK: u8 = 0x02;
+const V2_MAGIC_SESSION_BEGIN: u8 = 0x03;
+const V2_MAGIC_SESSION_END: u8 = 0x04;
+
 /// V2 of the binary protocol.
-/// This version is currently not in use by any device and is subject to change.
-#[allow(dead_code)]
+///
+/// Every message is exchanged within a session opened by [Protocol::session_begin].
 pub struct ProtocolV2<L: Link> {
     pub link: L,
+    /// The id of the open session, `0` if there is none.
     pub session_id: u32,
+    partial: PartialMessage,
+}
+
+impl<L: Link> ProtocolV2<L> {
+    pub fn new(link: L) -> Self {
+        ProtocolV2 {
+            link,
+            session_id: 0,
+            partial: PartialMessage::default(),
+        }
+    }
+
+    fn check_session(&self) -> Result<(), Error> {
+        if self.session_id == 0 {
+            return Err(Error::NoSession);
+        }
+        Ok(())
+    }
+}
+
+/// A message being read.
+///
+/// A read can be abandoned between two chunks, f.e. when a call is cancelled. The
+/// chunks read so far are kept, so the next read resumes the message instead of
+/// starting in the middle of its payload.
+#[derive(Default)]
+struct PartialMessage {
+    /// The type and the length of the message, once its first chunk was read.
+    header: Option<(MessageType, usize)>,
+    data: Vec<u8>,
+    /// The sequence number of the next V2 chunk.
+    seq: u32,
+}
+
+impl PartialMessage {
+    fn start(&mut self, message_type: MessageType, length: usize, data: &[u8]) {
+        self.header = Some((message_type, length));
+        self.data = data.to_vec();
+        self.seq = 0;
+    }
+
+    /// The length of the message, `None` before its first chunk.
+    fn length(&self) -> Option<usize> {
+        self.header.map(|(_, length)| length)
+    }
+
+    fn take(&mut self) -> ProtoMessage {
+        let (message_type, length) = self.header.take().expect("a started message");
+        let mut data = std::mem::take(&mut self.data);
+        data.truncate(length);
+        ProtoMessage(message_type, data)
+    }
+}
+
+/// Check the magic and the session id of a received V2 chunk.
+fn check_v2_chunk(
+    chunk: &[u8],
+    session_id: u32,
+    magic: u8,
+    header_len: usize,
+) -> Result<(), Error> {
+    if chunk.len() < header_len {
+        return Err(Error::UnexpectedChunkSizeFromDevice(chunk.len()));
+    }
+    if chunk[0] != magic {
+        return Err(Error::DeviceBadMagic);
+    }
+    if BigEndian::read_u32(&chunk[1..5]) != session_id {
+        return Err(Error::DeviceBadSessionId);
+    }
+    Ok(())
+}
+
+/// Read a V2 message, resuming the partial one if any.
+async fn read_v2<L: Link + Send>(
+    link: &mut L,
+    session_id: u32,
+    partial: &mut PartialMessage,
+) -> Result<ProtoMessage, Error> {
+    let res = read_v2_chunks(link, session_id, partial).await;
+    // The rest of a broken message is no use.
+    if res.is_err() {
+        *partial = PartialMessage::default();
+    }
+    res
+}
+
+async fn read_v2_chunks<L: Link + Send>(
+    link: &mut L,
+    session_id: u32,
+    partial: &mut PartialMessage,
+) -> Result<ProtoMessage, Error> {
+    if partial.header.is_none() {
+        let chunk = link.read_chunk().await?;
+        check_v2_chunk(&chunk, session_id, V2_MAGIC_FIRST_CHUNK, 13)?;
+        let message_type_id = BigEndian::read_u32(&chunk[5..9]);
+        let message_type = MessageType::from_i32(message_type_id as i32)
+            .ok_or(Error::InvalidMessageType(message_type_id))?;
+        let data_length = BigEndian::read_u32(&chunk[9..13]) as usize;
+        trace!("v2 read: {:?} of {} bytes", message_type, data_length);
+        partial.start(message_type, data_length, &chunk[13..]);
+    }
+
+    while Some(partial.data.len()) < partial.length() {
+        let chunk = link.read_chunk().await?;
+        check_v2_chunk(&chunk, session_id, V2_MAGIC_NEXT_CHUNK, 9)?;
+        if BigEndian::read_u32(&chunk[5..9]) != partial.seq {
+            return Err(Error::DeviceUnexpectedSequenceNumber);
+        }
+        partial.seq += 1;
+
+        partial.data.extend(&chunk[9..]);
+    }
+
+    Ok(partial.take())
 }
 
 #[async_trait]
 impl<L: Link + Send> Protocol for ProtocolV2<L> {
     async fn session_begin(&mut self) -> Result<(), Error> {
         let mut chunk = vec![0; REPLEN];
-        chunk[0] = 0x03;
+        chunk[0] = V2_MAGIC_SESSION_BEGIN;
         self.link.write_chunk(chunk).await?;
         let resp = self.link.read_chunk().await?;
-        if resp[0] != 0x03 {
-            println!(
-                "bad magic in v2 session_begin: {:x} instead of 0x03",
-                resp[0]
-            );
-            return Err(Error::DeviceBadMagic);
-        }
-        self.session_id = BigEndian::read_u32(&resp[1..5]);
+        self.session_id = parse_session_begin(&resp)?;
+        debug!("v2 session {} begun", self.session_id);
         Ok(())
     }
 
     async fn session_end(&mut self) -> Result<(), Error> {
-        assert!(self.session_id != 0);
+        self.check_session()?;
         let mut chunk = vec![0; REPLEN];
-        chunk[0] = 0x04;
+        chunk[0] = V2_MAGIC_SESSION_END;
         BigEndian::write_u32(&mut chunk[1..5], self.session_id);
         self.link.write_chunk(chunk).await?;
         let resp = self.link.read_chunk().await?;
-        if resp[0] != 0x04 {
-            println!("bad magic in v2 session_end: {:x} instead of 0x04", resp[0]);
+        if resp.first() != Some(&V2_MAGIC_SESSION_END) {
             return Err(Error::DeviceBadMagic);
         }
+        debug!("v2 session {} ended", self.session_id);
         self.session_id = 0;
         Ok(())
     }
 
     async fn write(&mut self, message: ProtoMessage) -> Result<(), Error> {
-        assert!(self.session_id != 0);
+        self.check_session()?;
+        trace!(
+            "v2 write: {:?} of {} bytes",
+            message.message_type(),
+            message.payload().len()
+        );
 
         // First generate the total payload, then write it to the transport in chunks.
         let mut data = vec![0; 8];
@@ -83,12 +238,12 @@ impl<L: Link + Send> Protocol for ProtocolV2<L> {
             // Build header.
             let mut chunk = if seq < 0 {
                 let mut header = vec![0; 5];
-                header[0] = 0x01;
+                header[0] = V2_MAGIC_FIRST_CHUNK;
                 BigEndian::write_u32(&mut header[1..5], self.session_id);
                 header
             } else {
                 let mut header = vec![0; 9];
-                header[0] = 0x01;
+                header[0] = V2_MAGIC_NEXT_CHUNK;
                 BigEndian::write_u32(&mut header[1..5], self.session_id);
                 BigEndian::write_u32(&mut header[5..9], seq as u32);
                 header
@@ -109,50 +264,264 @@ impl<L: Link + Send> Protocol for ProtocolV2<L> {
     }
 
     async fn read(&mut self) -> Result<ProtoMessage, Error> {
-        debug_assert!(self.session_id != 0);
+        self.check_session()?;
+        read_v2(&mut self.link, self.session_id, &mut self.partial).await
+    }
+}
 
-        let chunk = self.link.read_chunk().await?;
-        if chunk[0] != 0x01 {
-            println!("bad magic in v2 read: {:x} instead of 0x01", chunk[0]);
-            return Err(Error::DeviceBadMagic);
+/// Parse the device response to a V2 session begin request.
+fn parse_session_begin(resp: &[u8]) -> Result<u32, Error> {
+    if resp.len() < 5 {
+        return Err(Error::UnexpectedChunkSizeFromDevice(resp.len()));
+    }
+    if resp[0] != V2_MAGIC_SESSION_BEGIN {
+        return Err(Error::DeviceBadMagic);
+    }
+    match BigEndian::read_u32(&resp[1..5]) {
+        0 => Err(Error::DeviceBadSessionId),
+        session_id => Ok(session_id),
+    }
+}
+
+/// The versions of the binary protocol.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ProtocolVersion {
+    V1,
+    V2,
+}
+
+/// A protocol speaking the version supported by the device.
+///
+/// V1 is spoken unless V2 is enabled with [NegotiatedProtocol::set_probe_v2]. The
+/// version is negotiated then on the first session begin, or on the first message
+/// if no session was begun explicitly: the device is asked to open a V2 session,
+/// and V1 is used if it doesn't answer within [NEGOTIATION_TIMEOUT_MS], or answers
+/// with a V1 message. Trezor One drops the request silently, trezor-core answers
+/// with a V1 `Failure`.
+pub struct NegotiatedProtocol<L: Link> {
+    link: L,
+    version: Option<ProtocolVersion>,
+    /// Whether to ask the device for a V2 session.
+    probe_v2: bool,
+    /// The id of the open V2 session, `0` if there is none.
+    session_id: u32,
+    partial: PartialMessage,
+}
+
+impl<L: Link + Send> NegotiatedProtocol<L> {
+    pub fn new(link: L) -> Self {
+        NegotiatedProtocol {
+            link,
+            version: None,
+            probe_v2: false,
+            session_id: 0,
+            partial: PartialMessage::default(),
+        }
+    }
+
+    /// Whether to ask the device for a V2 session. It only applies before the version
+    /// is negotiated.
+    pub fn set_probe_v2(&mut self, probe_v2: bool) {
+        self.probe_v2 = probe_v2;
+    }
+
+    /// The negotiated version, `None` if the device wasn't talked to yet.
+    pub fn version(&self) -> Option<ProtocolVersion> {
+        self.version
+    }
+
+    pub fn link_mut(&mut self) -> &mut L {
+        &mut self.link
+    }
+
+    async fn negotiate(&mut self) -> Result<ProtocolVersion, Error> {
+        if let Some(version) = self.version {
+            return Ok(version);
         }
-        if BigEndian::read_u32(&chunk[1..5]) != self.session_id {
-            return Err(Error::DeviceBadSessionId);
+        if !self.probe_v2 {
+            self.version = Some(ProtocolVersion::V1);
+            return Ok(ProtocolVersion::V1);
         }
-        let message_type_id = BigEndian::read_u32(&chunk[5..9]);
-        let message_type = MessageType::from_i32(message_type_id as i32)
-            .ok_or(Error::InvalidMessageType(message_type_id))?;
-        let data_length = BigEndian::read_u32(&chunk[9..13]) as usize;
 
-        let mut data: Vec<u8> = chunk[13..].into();
-        let mut seq = 0;
-        while data.len() < data_length {
-            let chunk = self.link.read_chunk().await?;
-            if chunk[0] != 0x02 {
-                println!(
-                    "bad magic in v2 session_begin: {:x} instead of 0x02",
-                    chunk[0]
+        let mut chunk = vec![0; REPLEN];
+        chunk[0] = V2_MAGIC_SESSION_BEGIN;
+        self.link.write_chunk(chunk).await?;
+        let timeout = Duration::from_millis(NEGOTIATION_TIMEOUT_MS);
+        let version = match self.link.read_chunk_timeout(timeout).await? {
+            // trezor-core answers the unknown report with a V1 `Failure`.
+            Some(resp) if is_v1_first_chunk(&resp) => {
+                start_v1(&mut self.partial, &resp)?;
+                let message = read_v1(&mut self.link, &mut self.partial).await?;
+                debug!(
+                    "device answered the v2 probe with {:?}",
+                    message.message_type()
                 );
-                return Err(Error::DeviceBadMagic);
+                ProtocolVersion::V1
             }
-            if BigEndian::read_u32(&chunk[1..5]) != self.session_id {
-                return Err(Error::DeviceBadSessionId);
+            Some(resp) => {
+                self.session_id = parse_session_begin(&resp)?;
+                ProtocolVersion::V2
             }
-            if BigEndian::read_u32(&chunk[5..9]) != seq as u32 {
-                return Err(Error::DeviceUnexpectedSequenceNumber);
+            None => ProtocolVersion::V1,
+        };
+        debug!("negotiated protocol {:?}", version);
+        self.version = Some(version);
+        Ok(version)
+    }
+
+    /// Make sure there is a session to exchange messages in.
+    async fn ensure_session(&mut self) -> Result<ProtocolVersion, Error> {
+        let version = self.negotiate().await?;
+        if version == ProtocolVersion::V2 && self.session_id == 0 {
+            let mut v2 = self.v2();
+            v2.session_begin().await?;
+            self.session_id = v2.session_id;
+        }
+        Ok(version)
+    }
+
+    fn v2(&mut self) -> ProtocolV2<&mut L> {
+        ProtocolV2 {
+            link: &mut self.link,
+            session_id: self.session_id,
+            // The messages are read by `read_v2` with the partial message of `self`.
+            partial: PartialMessage::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<L: Link + Send> Protocol for NegotiatedProtocol<L> {
+    /// Does nothing if a session is open already.
+    async fn session_begin(&mut self) -> Result<(), Error> {
+        self.ensure_session().await?;
+        Ok(())
+    }
+
+    async fn session_end(&mut self) -> Result<(), Error> {
+        match self.version {
+            Some(ProtocolVersion::V2) if self.session_id != 0 => {
+                let mut v2 = self.v2();
+                v2.session_end().await?;
+                self.session_id = v2.session_id;
+                Ok(())
             }
-            seq += 1;
+            _ => Ok(()),
+        }
+    }
+
+    async fn write(&mut self, message: ProtoMessage) -> Result<(), Error> {
+        match self.ensure_session().await? {
+            ProtocolVersion::V1 => ProtocolV1::new(&mut self.link).write(message).await,
+            ProtocolVersion::V2 => self.v2().write(message).await,
+        }
+    }
+
+    async fn read(&mut self) -> Result<ProtoMessage, Error> {
+        match self.ensure_session().await? {
+            ProtocolVersion::V1 => read_v1(&mut self.link, &mut self.partial).await,
+            ProtocolVersion::V2 => {
+                read_v2(&mut self.link, self.session_id, &mut self.partial).await
+            }
+        }
+    }
+}
+
+/// Split a message into the chunks of the original binary protocol.
+pub(crate) fn v1_chunks(message: ProtoMessage) -> Vec<Vec<u8>> {
+    // First generate the total payload, then split it in chunks.
+    let mut data = vec![0; 8];
+    data[0] = 0x23;
+    data[1] = 0x23;
+    BigEndian::write_u16(&mut data[2..4], message.message_type() as u16);
+    BigEndian::write_u32(&mut data[4..8], message.payload().len() as u32);
+    data.extend(message.into_payload());
+
+    data.chunks(REPLEN - 1)
+        .map(|part| {
+            let mut chunk = vec![0x3f];
+            chunk.extend(part);
+            chunk.resize(REPLEN, 0);
+            chunk
+        })
+        .collect()
+}
+
+fn is_v1_first_chunk(chunk: &[u8]) -> bool {
+    chunk.len() >= 9 && chunk[..3] == [0x3f, 0x23, 0x23]
+}
+
+/// Start a V1 message from its first chunk.
+fn start_v1(partial: &mut PartialMessage, chunk: &[u8]) -> Result<(), Error> {
+    if !is_v1_first_chunk(chunk) {
+        warn!(
+            "bad magic in v1 read: {:x?} instead of 0x3f2323",
+            &chunk[..cmp::min(chunk.len(), 3)]
+        );
+        return Err(Error::DeviceBadMagic);
+    }
+    let message_type_id = BigEndian::read_u16(&chunk[3..5]) as u32;
+    let message_type = MessageType::from_i32(message_type_id as i32)
+        .ok_or(Error::InvalidMessageType(message_type_id))?;
+    let data_length = BigEndian::read_u32(&chunk[5..9]) as usize;
+    trace!("v1 read: {:?} of {} bytes", message_type, data_length);
+    partial.start(message_type, data_length, &chunk[9..]);
+    Ok(())
+}
+
+/// Read a V1 message, resuming the partial one if any.
+async fn read_v1<L: Link + Send>(
+    link: &mut L,
+    partial: &mut PartialMessage,
+) -> Result<ProtoMessage, Error> {
+    let res = read_v1_chunks(link, partial).await;
+    // The rest of a broken message is no use.
+    if res.is_err() {
+        *partial = PartialMessage::default();
+    }
+    res
+}
+
+async fn read_v1_chunks<L: Link + Send>(
+    link: &mut L,
+    partial: &mut PartialMessage,
+) -> Result<ProtoMessage, Error> {
+    if partial.header.is_none() {
+        let chunk = link.read_chunk().await?;
+        start_v1(partial, &chunk)?;
+    }
 
-            data.extend(&chunk[9..]);
+    while Some(partial.data.len()) < partial.length() {
+        let chunk = link.read_chunk().await?;
+        if chunk.first() != Some(&0x3f) {
+            warn!("bad magic in v1 read: {:x?} instead of 0x3f", chunk.first());
+            return Err(Error::DeviceBadMagic);
         }
 
-        Ok(ProtoMessage(message_type, data[0..data_length].into()))
+        partial.data.extend(&chunk[1..]);
+        trace!(
+            "v1 read: {} of {:?} bytes",
+            partial.data.len(),
+            partial.length()
+        );
     }
+
+    Ok(partial.take())
 }
 
 /// The original binary protocol.
 pub struct ProtocolV1<L: Link> {
     pub link: L,
+    partial: PartialMessage,
+}
+
+impl<L: Link> ProtocolV1<L> {
+    pub fn new(link: L) -> Self {
+        ProtocolV1 {
+            link,
+            partial: PartialMessage::default(),
+        }
+    }
 }
 
 #[async_trait]
@@ -166,69 +535,281 @@ impl<L: Link + Send> Protocol for ProtocolV1<L> {
     }
 
     async fn write(&mut self, message: ProtoMessage) -> Result<(), Error> {
-        // First generate the total payload, then write it to the transport in chunks.
-        let mut data = vec![0; 8];
-        data[0] = 0x23;
-        data[1] = 0x23;
-        BigEndian::write_u16(&mut data[2..4], message.message_type() as u16);
-        BigEndian::write_u32(&mut data[4..8], message.payload().len() as u32);
-        data.extend(message.into_payload());
-
-        let mut cur: usize = 0;
-        while cur < data.len() {
-            let mut chunk = vec![0x3f];
-            let end = cmp::min(cur + (REPLEN - 1), data.len());
-            chunk.extend(&data[cur..end]);
-            cur = end;
-            debug_assert!(chunk.len() <= REPLEN);
-            chunk.resize(REPLEN, 0);
-
+        trace!(
+            "v1 write: {:?} of {} bytes",
+            message.message_type(),
+            message.payload().len()
+        );
+        for chunk in v1_chunks(message) {
             self.link.write_chunk(chunk).await?;
         }
-
         Ok(())
     }
 
     async fn read(&mut self) -> Result<ProtoMessage, Error> {
-        #[cfg(target_arch = "wasm32")]
-        crate::console_log!("read() start");
-
-        let chunk = self.link.read_chunk().await?;
-        if chunk[0] != 0x3f || chunk[1] != 0x23 || chunk[2] != 0x23 {
-            println!(
-                "bad magic in v1 read: {:x}{:x}{:x} instead of 0x3f2323",
-                chunk[0], chunk[1], chunk[2]
-            );
-            return Err(Error::DeviceBadMagic);
+        read_v1(&mut self.link, &mut self.partial).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::collections::VecDeque;
+
+    /// A link that records written chunks and reads from a queue of prepared chunks.
+    #[derive(Default)]
+    struct MemoryLink {
+        written: Vec<Vec<u8>>,
+        to_read: VecDeque<Vec<u8>>,
+    }
+
+    #[async_trait]
+    impl Link for MemoryLink {
+        async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
+            self.written.push(chunk);
+            Ok(())
         }
-        let message_type_id = BigEndian::read_u16(&chunk[3..5]) as u32;
-        let message_type = MessageType::from_i32(message_type_id as i32)
-            .ok_or(Error::InvalidMessageType(message_type_id))?;
-        let data_length = BigEndian::read_u32(&chunk[5..9]) as usize;
-        let mut data: Vec<u8> = chunk[9..].into();
-
-        #[cfg(target_arch = "wasm32")]
-        crate::console_log!(
-            "read() expected len: {}, actual len: {}",
-            data_length,
-            data.len()
-        );
 
-        while data.len() < data_length {
-            let chunk = self.link.read_chunk().await?;
-            if chunk[0] != 0x3f {
-                println!("bad magic in v1 read: {:x} instead of 0x3f", chunk[0]);
-                return Err(Error::DeviceBadMagic);
+        /// Waits forever when there is nothing to read, like an idle device.
+        async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
+            match self.to_read.pop_front() {
+                Some(chunk) => Ok(chunk),
+                None => future::pending().await,
             }
+        }
 
-            data.extend(&chunk[1..]);
-            #[cfg(target_arch = "wasm32")]
-            crate::console_log!("read() A total len: {}", data.len());
+        async fn read_chunk_timeout(&mut self, _: Duration) -> Result<Option<Vec<u8>>, Error> {
+            Ok(self.to_read.pop_front())
         }
+    }
+
+    const SESSION_ID: u32 = 0x0102_0304;
+
+    fn session_chunk(magic: u8) -> Vec<u8> {
+        let mut chunk = vec![0; REPLEN];
+        chunk[0] = magic;
+        BigEndian::write_u32(&mut chunk[1..5], SESSION_ID);
+        chunk
+    }
+
+    /// Encode a message with the V2 framing, as the device would send it.
+    fn v2_chunks(message_type: MessageType, payload: &[u8]) -> Vec<Vec<u8>> {
+        let mut first = session_chunk(V2_MAGIC_FIRST_CHUNK);
+        BigEndian::write_u32(&mut first[5..9], message_type as u32);
+        BigEndian::write_u32(&mut first[9..13], payload.len() as u32);
+        let (head, mut rest) = payload.split_at(cmp::min(payload.len(), REPLEN - 13));
+        first[13..13 + head.len()].copy_from_slice(head);
 
-        #[cfg(target_arch = "wasm32")]
-        crate::console_log!("read() end");
+        let mut chunks = vec![first];
+        let mut seq = 0;
+        while !rest.is_empty() {
+            let mut chunk = session_chunk(V2_MAGIC_NEXT_CHUNK);
+            BigEndian::write_u32(&mut chunk[5..9], seq);
+            let (head, tail) = rest.split_at(cmp::min(rest.len(), REPLEN - 9));
+            chunk[9..9 + head.len()].copy_from_slice(head);
+            chunks.push(chunk);
+            rest = tail;
+            seq += 1;
+        }
+        chunks
+    }
+
+    fn v2_with_session(to_read: Vec<Vec<u8>>) -> ProtocolV2<MemoryLink> {
+        ProtocolV2 {
+            link: MemoryLink {
+                to_read: to_read.into(),
+                ..MemoryLink::default()
+            },
+            session_id: SESSION_ID,
+            partial: PartialMessage::default(),
+        }
+    }
+
+    #[test]
+    fn test_v2_session_begin_end() {
+        let mut link = MemoryLink::default();
+        link.to_read
+            .push_back(session_chunk(V2_MAGIC_SESSION_BEGIN));
+        link.to_read.push_back(session_chunk(V2_MAGIC_SESSION_END));
+        let mut protocol = ProtocolV2::new(link);
+
+        block_on(protocol.session_begin()).unwrap();
+        assert_eq!(protocol.session_id, SESSION_ID);
+        block_on(protocol.session_end()).unwrap();
+        assert_eq!(protocol.session_id, 0);
+
+        let written = &protocol.link.written;
+        assert_eq!(written[0][0], V2_MAGIC_SESSION_BEGIN);
+        assert_eq!(written[1][0], V2_MAGIC_SESSION_END);
+        assert_eq!(BigEndian::read_u32(&written[1][1..5]), SESSION_ID);
+    }
+
+    #[test]
+    fn test_v2_no_session() {
+        let mut protocol = ProtocolV2::new(MemoryLink::default());
+        let message = ProtoMessage(MessageType::MessageType_Ping, vec![]);
+        assert!(matches!(
+            block_on(protocol.write(message)),
+            Err(Error::NoSession)
+        ));
+        assert!(matches!(block_on(protocol.read()), Err(Error::NoSession)));
+        assert!(matches!(
+            block_on(protocol.session_end()),
+            Err(Error::NoSession)
+        ));
+    }
+
+    #[test]
+    fn test_v2_write_chunks() {
+        let payload: Vec<u8> = (0..100).collect();
+        let mut protocol = v2_with_session(vec![]);
+        let message = ProtoMessage(MessageType::MessageType_Ping, payload.clone());
+        block_on(protocol.write(message)).unwrap();
+
+        // The device receives the same framing it sends.
+        assert_eq!(
+            protocol.link.written,
+            v2_chunks(MessageType::MessageType_Ping, &payload)
+        );
+    }
+
+    #[test]
+    fn test_v2_read_chunks() {
+        let payload: Vec<u8> = (0..200).collect();
+        let mut protocol = v2_with_session(v2_chunks(MessageType::MessageType_Success, &payload));
+        let message = block_on(protocol.read()).unwrap();
+        assert_eq!(message.message_type(), MessageType::MessageType_Success);
+        assert_eq!(message.payload(), &payload[..]);
+    }
+
+    #[test]
+    fn test_v2_read_errors() {
+        let mut chunks = v2_chunks(MessageType::MessageType_Success, &[0; 100]);
+        chunks[0][0] = 0x3f;
+        let mut protocol = v2_with_session(chunks);
+        assert!(matches!(
+            block_on(protocol.read()),
+            Err(Error::DeviceBadMagic)
+        ));
+
+        let mut chunks = v2_chunks(MessageType::MessageType_Success, &[0; 100]);
+        chunks[1][4] ^= 0xff;
+        let mut protocol = v2_with_session(chunks);
+        assert!(matches!(
+            block_on(protocol.read()),
+            Err(Error::DeviceBadSessionId)
+        ));
+
+        let mut chunks = v2_chunks(MessageType::MessageType_Success, &[0; 100]);
+        chunks[1][8] = 1;
+        let mut protocol = v2_with_session(chunks);
+        assert!(matches!(
+            block_on(protocol.read()),
+            Err(Error::DeviceUnexpectedSequenceNumber)
+        ));
+
+        let mut protocol = v2_with_session(vec![vec![V2_MAGIC_FIRST_CHUNK; 4]]);
+        assert!(matches!(
+            block_on(protocol.read()),
+            Err(Error::UnexpectedChunkSizeFromDevice(4))
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_v2() {
+        let mut link = MemoryLink::default();
+        link.to_read
+            .push_back(session_chunk(V2_MAGIC_SESSION_BEGIN));
+        link.to_read
+            .extend(v2_chunks(MessageType::MessageType_Success, b"pong"));
+        let mut protocol = NegotiatedProtocol::new(link);
+        protocol.set_probe_v2(true);
+
+        block_on(protocol.session_begin()).unwrap();
+        assert_eq!(protocol.version(), Some(ProtocolVersion::V2));
+        // A session is open already, nothing is sent.
+        block_on(protocol.session_begin()).unwrap();
+        assert_eq!(protocol.link.written.len(), 1);
+
+        let message = ProtoMessage(MessageType::MessageType_Ping, b"ping".to_vec());
+        block_on(protocol.write(message)).unwrap();
+        assert_eq!(protocol.link.written[1][0], V2_MAGIC_FIRST_CHUNK);
+        let message = block_on(protocol.read()).unwrap();
+        assert_eq!(message.payload(), b"pong");
+    }
+
+    #[test]
+    fn test_negotiate_v1() {
+        let mut protocol = NegotiatedProtocol::new(MemoryLink::default());
+        protocol.set_probe_v2(true);
+
+        let message = ProtoMessage(MessageType::MessageType_Ping, b"ping".to_vec());
+        block_on(protocol.write(message)).unwrap();
+        assert_eq!(protocol.version(), Some(ProtocolVersion::V1));
+
+        let written = &protocol.link.written;
+        assert_eq!(written.len(), 2);
+        assert_eq!(written[0][0], V2_MAGIC_SESSION_BEGIN);
+        assert_eq!(&written[1][..3], &[0x3f, 0x23, 0x23]);
+    }
+
+    #[test]
+    fn test_negotiate_v1_failure() {
+        // trezor-core answers the probe with a V1 `Failure`, in two chunks here.
+        let payload = vec![0x0a; 80];
+        let mut link = MemoryLink::default();
+        link.to_read.extend(v1_chunks(ProtoMessage(
+            MessageType::MessageType_Failure,
+            payload,
+        )));
+        link.to_read.extend(v1_chunks(ProtoMessage(
+            MessageType::MessageType_Success,
+            b"pong".to_vec(),
+        )));
+        let mut protocol = NegotiatedProtocol::new(link);
+        protocol.set_probe_v2(true);
+
+        let message = ProtoMessage(MessageType::MessageType_Ping, b"ping".to_vec());
+        block_on(protocol.write(message)).unwrap();
+        assert_eq!(protocol.version(), Some(ProtocolVersion::V1));
+        let message = block_on(protocol.read()).unwrap();
+        assert_eq!(message.message_type(), MessageType::MessageType_Success);
+        assert_eq!(message.payload(), b"pong");
+    }
+
+    #[test]
+    fn test_no_probe() {
+        let mut protocol = NegotiatedProtocol::new(MemoryLink::default());
+
+        let message = ProtoMessage(MessageType::MessageType_Ping, b"ping".to_vec());
+        block_on(protocol.write(message)).unwrap();
+        assert_eq!(protocol.version(), Some(ProtocolVersion::V1));
+        assert_eq!(&protocol.link.written[0][..3], &[0x3f, 0x23, 0x23]);
+    }
 
-        Ok(ProtoMessage(message_type, data[0..data_length].into()))
+    #[test]
+    fn test_abandoned_read_resumes() {
+        use futures::FutureExt;
+
+        let payload: Vec<u8> = (0..100).collect();
+        let chunks = v1_chunks(ProtoMessage(
+            MessageType::MessageType_Success,
+            payload.clone(),
+        ));
+        let mut protocol = ProtocolV1::new(MemoryLink::default());
+        protocol.link.to_read.push_back(chunks[0].clone());
+        // The read is abandoned while waiting for the second chunk.
+        assert!(protocol.read().now_or_never().is_none());
+        protocol.link.to_read.push_back(chunks[1].clone());
+        let message = block_on(protocol.read()).unwrap();
+        assert_eq!(message.payload(), &payload[..]);
+
+        let chunks = v2_chunks(MessageType::MessageType_Success, &payload);
+        let mut protocol = v2_with_session(vec![chunks[0].clone()]);
+        assert!(protocol.read().now_or_never().is_none());
+        protocol.link.to_read.push_back(chunks[1].clone());
+        let message = block_on(protocol.read()).unwrap();
+        assert_eq!(message.payload(), &payload[..]);
     }
 }
@@ -0,0 +1,287 @@
+//! # Traffic recording
+//!
+//! [RecordingTransport] captures the conversation with a device as JSON lines, one
+//! [CapturedMessage] per line:
+//!
+//! ```text
+//! {"timestamp_ms":1634567890123,"direction":"write","message_type":"MessageType_Ping","payload":"0a0568656c6c6f"}
+//! {"timestamp_ms":1634567890135,"direction":"read","message_type":"MessageType_Success","payload":"0a0568656c6c6f"}
+//! ```
+//!
+//! [ReplayTransport] plays a capture back in place of the device, so a recorded
+//! session can be reproduced in a test.
+
+use async_trait::async_trait;
+use protobuf::ProtobufEnum;
+use std::io::{BufRead, Write};
+
+use super::mock::{MockTransport, Script};
+use super::{Error, ProtoMessage, Transport, TransportConfig};
+use crate::protos::MessageType;
+use crate::time;
+
+/// Which way a message went.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Sent by the host to the device.
+    Write,
+    /// Received by the host from the device.
+    Read,
+}
+
+/// A message of a capture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedMessage {
+    /// When the message was exchanged, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    pub direction: Direction,
+    pub message: ProtoMessage,
+}
+
+/// A line of a capture.
+#[derive(Serialize, Deserialize)]
+struct CaptureLine {
+    timestamp_ms: u64,
+    direction: Direction,
+    /// The name of the message type, as in the `MessageType` enum.
+    message_type: String,
+    /// The hex-encoded protobuf payload.
+    payload: String,
+}
+
+impl CapturedMessage {
+    /// Encode the message as a line of a capture, without the line break.
+    pub fn to_json(&self) -> String {
+        let line = CaptureLine {
+            timestamp_ms: self.timestamp_ms,
+            direction: self.direction,
+            message_type: format!("{:?}", self.message.message_type()),
+            payload: hex::encode(self.message.payload()),
+        };
+        serde_json::to_string(&line).expect("a capture line is valid JSON")
+    }
+
+    /// Decode a line of a capture.
+    pub fn from_json(line: &str) -> Result<CapturedMessage, Error> {
+        let line: CaptureLine = serde_json::from_str(line)
+            .map_err(|e| Error::Capture(format!("invalid capture line: {}", e)))?;
+        let message_type = MessageType::values()
+            .iter()
+            .find(|t| format!("{:?}", t) == line.message_type)
+            .ok_or_else(|| {
+                Error::Capture(format!("unknown message type: {}", line.message_type))
+            })?;
+        let payload = hex::decode(&line.payload)
+            .map_err(|e| Error::Capture(format!("invalid payload: {}", e)))?;
+        Ok(CapturedMessage {
+            timestamp_ms: line.timestamp_ms,
+            direction: line.direction,
+            message: ProtoMessage(*message_type, payload),
+        })
+    }
+}
+
+/// Read all the messages of a capture.
+pub fn read_capture<R: BufRead>(capture: R) -> Result<Vec<CapturedMessage>, Error> {
+    let mut messages = Vec::new();
+    for line in capture.lines() {
+        let line = line.map_err(|e| Error::Capture(e.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        messages.push(CapturedMessage::from_json(&line)?);
+    }
+    Ok(messages)
+}
+
+/// A transport recording the messages exchanged over another transport.
+///
+/// Every message is written to the capture as soon as it is exchanged, so the capture
+/// is complete up to a crash. The sessions are not recorded.
+///
+/// Unlike the logs, the capture is not redacted: it holds the PINs, passphrases and
+/// other secrets sent to the device, keep it private.
+pub struct RecordingTransport<W> {
+    transport: Box<dyn Transport>,
+    capture: W,
+}
+
+impl<W: Write + Send> RecordingTransport<W> {
+    pub fn new(transport: Box<dyn Transport>, capture: W) -> RecordingTransport<W> {
+        RecordingTransport { transport, capture }
+    }
+
+    /// Stop recording and get the capture back.
+    pub fn into_capture(self) -> W {
+        self.capture
+    }
+
+    fn record(&mut self, direction: Direction, message: &ProtoMessage) -> Result<(), Error> {
+        let captured = CapturedMessage {
+            timestamp_ms: time::unix_millis(),
+            direction,
+            message: message.clone(),
+        };
+        writeln!(self.capture, "{}", captured.to_json())
+            .and_then(|_| self.capture.flush())
+            .map_err(|e| Error::Capture(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl<W: Write + Send> Transport for RecordingTransport<W> {
+    async fn session_begin(&mut self) -> Result<(), Error> {
+        self.transport.session_begin().await
+    }
+    async fn session_end(&mut self) -> Result<(), Error> {
+        self.transport.session_end().await
+    }
+
+    async fn write_message(&mut self, message: ProtoMessage) -> Result<(), Error> {
+        self.record(Direction::Write, &message)?;
+        self.transport.write_message(message).await
+    }
+    async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
+        let message = self.transport.read_message().await?;
+        self.record(Direction::Read, &message)?;
+        Ok(message)
+    }
+
+    fn set_config(&mut self, config: &TransportConfig) {
+        self.transport.set_config(config);
+    }
+}
+
+/// A transport playing a capture back in place of the device.
+///
+/// The host must send the recorded messages in the recorded order, and gets the
+/// recorded responses.
+pub struct ReplayTransport {
+    script: Script,
+    transport: MockTransport,
+}
+
+impl ReplayTransport {
+    /// Play back the capture, the host must send the exact recorded messages.
+    pub fn new(messages: Vec<CapturedMessage>) -> ReplayTransport {
+        ReplayTransport::with_script(messages, |script, message| script.expect_raw(message))
+    }
+
+    /// Play back the capture, the host must send messages of the recorded types.
+    ///
+    /// Useful when the host sends random data, f.e. in `EntropyAck`.
+    pub fn new_lenient(messages: Vec<CapturedMessage>) -> ReplayTransport {
+        ReplayTransport::with_script(messages, |script, message| {
+            script.expect_type(message.message_type())
+        })
+    }
+
+    fn with_script<F>(messages: Vec<CapturedMessage>, expect: F) -> ReplayTransport
+    where
+        F: Fn(Script, ProtoMessage) -> Script,
+    {
+        let script = messages
+            .into_iter()
+            .fold(Script::new(), |script, captured| match captured.direction {
+                Direction::Write => expect(script, captured.message),
+                Direction::Read => script.respond_raw(captured.message),
+            });
+        ReplayTransport {
+            script: script.clone(),
+            transport: MockTransport::new(script),
+        }
+    }
+
+    /// The remaining conversation, keep a clone to check it was played to the end.
+    pub fn script(&self) -> Script {
+        self.script.clone()
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn session_begin(&mut self) -> Result<(), Error> {
+        self.transport.session_begin().await
+    }
+    async fn session_end(&mut self) -> Result<(), Error> {
+        self.transport.session_end().await
+    }
+
+    async fn write_message(&mut self, message: ProtoMessage) -> Result<(), Error> {
+        self.transport.write_message(message).await
+    }
+    async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
+        self.transport.read_message().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos;
+    use crate::{trezor_with_transport, TrezorModel};
+    use futures::executor::block_on;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// A capture kept in memory, shared with the test.
+    #[derive(Clone, Default)]
+    struct SharedCapture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedCapture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut pong = protos::Success::new();
+        pong.set_message("hello".to_owned());
+        let mut ping = protos::Ping::new();
+        ping.set_message("hello".to_owned());
+        let device = MockTransport::new(Script::new().expect(ping).respond(pong));
+
+        let capture = SharedCapture::default();
+        let recorder = RecordingTransport::new(Box::new(device), capture.clone());
+        let mut trezor = trezor_with_transport(TrezorModel::T, Box::new(recorder));
+        block_on(async { trezor.ping("hello").await?.ok() }).unwrap();
+
+        let messages = read_capture(&capture.0.lock().unwrap()[..]).unwrap();
+        let directions: Vec<_> = messages.iter().map(|m| m.direction).collect();
+        assert_eq!(directions, vec![Direction::Write, Direction::Read]);
+        assert_eq!(
+            messages[0].message.message_type(),
+            MessageType::MessageType_Ping
+        );
+
+        let replay = ReplayTransport::new(messages);
+        let script = replay.script();
+        let mut trezor = trezor_with_transport(TrezorModel::T, Box::new(replay));
+        let resp = block_on(async { trezor.ping("hello").await?.ok() });
+        assert!(resp.is_ok());
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_capture_line() {
+        let line = r#"{"timestamp_ms":1,"direction":"read","message_type":"MessageType_Success","payload":"0a026f6b"}"#;
+        let captured = CapturedMessage::from_json(line).unwrap();
+        assert_eq!(captured.direction, Direction::Read);
+        assert_eq!(
+            captured.message,
+            ProtoMessage(
+                MessageType::MessageType_Success,
+                vec![0x0a, 0x02, 0x6f, 0x6b]
+            )
+        );
+        assert_eq!(captured.to_json(), line);
+
+        let res = CapturedMessage::from_json(&line.replace("Success", "Nonsense"));
+        assert!(matches!(res, Err(Error::Capture(_))));
+    }
+}
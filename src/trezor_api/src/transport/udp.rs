@@ -1,18 +1,34 @@
 use async_trait::async_trait;
 use std::fmt;
+use std::io;
 use std::time::Duration;
-use std::net::UdpSocket;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
 
-use crate::{TrezorModel, AvailableDevice};
-use super::{Error, Transport, AvailableDeviceTransport, Link, Protocol, ProtocolV1, ProtoMessage};
+use crate::{DeviceMode, TrezorModel, AvailableDevice};
+use super::{Error, Transport, TransportConfig, AvailableDeviceTransport, Link, Protocol, NegotiatedProtocol, ProtoMessage};
+use super::blocking::IoThread;
 
 /// The chunk size for the serial protocol.
 const CHUNK_SIZE: usize = 64;
 
-const READ_TIMEOUT_MS: u64 = 100000;
-const WRITE_TIMEOUT_MS: u64 = 100000;
+/// How long to wait for the emulator to answer a ping.
+const PING_TIMEOUT_MS: u64 = 200;
+
+/// The address the emulator listens on by default.
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+pub const DEFAULT_PORT: usize = 21324;
+
+/// The debug link listens on the port following the main one.
+pub const DEBUG_PORT_OFFSET: usize = 1;
+
+/// The liveness datagrams of the emulator.
+pub const PING_DATAGRAM: &[u8] = b"PINGPING";
+pub const PONG_DATAGRAM: &[u8] = b"PONGPONG";
 
 /// An available transport for connecting with a device.
+///
+/// The port is the main one, the debug link is reached on the following port.
 #[derive(Debug)]
 pub struct AvailableUdpTransport {
     pub host: String,
@@ -23,6 +39,44 @@ impl AvailableUdpTransport {
     pub fn address(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    pub fn debug_address(&self) -> String {
+        format!("{}:{}", self.host, self.port + DEBUG_PORT_OFFSET)
+    }
+}
+
+/// Resolve the address and bind an ephemeral port of the same family, connected to it.
+fn connect_socket(address: &str) -> Result<UdpSocket, Error> {
+    let address = address.to_socket_addrs()
+        .map_err(Error::Udp)?
+        .next()
+        .ok_or(Error::DeviceNotFound)?;
+    let local = match address {
+        SocketAddr::V4(_) => "0.0.0.0:0",
+        SocketAddr::V6(_) => "[::]:0",
+    };
+
+    let socket = UdpSocket::bind(local).map_err(Error::Udp)?;
+    socket.connect(address).map_err(Error::Udp)?;
+    Ok(socket)
+}
+
+/// Check whether the emulator answers a ping on the given address.
+fn ping(address: &str) -> Result<bool, Error> {
+    let socket = connect_socket(address)?;
+    socket.set_read_timeout(Some(Duration::from_millis(PING_TIMEOUT_MS))).map_err(Error::Udp)?;
+    socket.send(PING_DATAGRAM).map_err(Error::Udp)?;
+
+    let mut resp = [0; 8];
+    match socket.recv(&mut resp) {
+        Ok(n) => Ok(&resp[..n] == PONG_DATAGRAM),
+        // Nothing listens on the port, or it doesn't answer in time.
+        Err(ref err) if matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::ConnectionRefused
+        ) => Ok(false),
+        Err(err) => Err(Error::Udp(err)),
+    }
 }
 
 impl fmt::Display for AvailableUdpTransport {
@@ -31,63 +85,109 @@ impl fmt::Display for AvailableUdpTransport {
     }
 }
 
+/// A link to an emulator. The reads run on the I/O thread of the link, which shares the socket.
 pub struct UdpLink {
-    socket: UdpSocket,
-    address: String,
+    socket: Arc<UdpSocket>,
+    io: IoThread,
+    read_timeout: Duration,
+}
+
+/// A read attempt for the I/O thread.
+fn read_attempt(socket: Arc<UdpSocket>) -> impl FnMut(Duration) -> Result<Option<Vec<u8>>, Error> + Send + 'static {
+    move |timeout| {
+        let mut chunk = vec![0; CHUNK_SIZE];
+        socket.set_read_timeout(Some(timeout)).map_err(Error::Udp)?;
+        match socket.recv(&mut chunk) {
+            Ok(CHUNK_SIZE) => Ok(Some(chunk)),
+            Ok(n) => Err(Error::UnexpectedChunkSizeFromDevice(n)),
+            Err(ref err)
+                if err.kind() == io::ErrorKind::WouldBlock || err.kind() == io::ErrorKind::TimedOut =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(Error::Udp(err)),
+        }
+    }
 }
 
 #[async_trait]
 impl Link for UdpLink {
     async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
         debug_assert_eq!(CHUNK_SIZE, chunk.len());
-        self.socket.send_to(&chunk, &self.address)
+        self.socket.send(&chunk)
             .map_err(|err| Error::Udp(err))?;
         Ok(())
     }
 
     async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
-        let mut chunk = vec![0; CHUNK_SIZE];
-
-        let n = self.socket.recv(&mut chunk)
-            .map_err(|err| Error::Udp(err))?;
+        self.io.read(self.read_timeout).await
+    }
 
-        if n == CHUNK_SIZE {
-            Ok(chunk)
-        } else {
-            Err(Error::DeviceReadTimeout)
-        }
+    async fn read_chunk_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<u8>>, Error> {
+        self.io.read_timeout(self.read_timeout, timeout).await
     }
 }
 
 /// An implementation of the Transport interface for UDP devices.
 pub struct UdpTransport {
-    protocol: ProtocolV1<UdpLink>,
+    protocol: NegotiatedProtocol<UdpLink>,
 }
 
 impl UdpTransport {
+    /// Find an emulator listening on the given host and port.
+    ///
+    /// The emulator is listed as a normal device, and as a debug device if its debug link
+    /// answers on the following port. The emulator doesn't tell its model over the liveness
+    /// check, so it is assumed to be a Trezor Model T.
+    pub fn find_devices(host: &str, port: usize) -> Result<Vec<AvailableDevice>, Error> {
+        let transport = AvailableUdpTransport {
+            host: host.to_owned(),
+            port,
+        };
+
+        let mut devices = Vec::new();
+        for (debug, address) in [(false, transport.address()), (true, transport.debug_address())] {
+            if ping(&address)? {
+                devices.push(AvailableDevice {
+                    model: TrezorModel::T,
+                    mode: DeviceMode::Normal,
+                    debug,
+                    transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                        host: host.to_owned(),
+                        port,
+                    }),
+                });
+            }
+        }
+        log::trace!("found {} devices over UDP at {}:{}", devices.len(), host, port);
+        Ok(devices)
+    }
+
     /// Connect to a device over the UDP transport.
     pub fn connect(device: &AvailableDevice) -> Result<Box<dyn Transport>, Error> {
         let transport = match device.transport {
             AvailableDeviceTransport::Udp(ref t) => t,
             _ => panic!("passed wrong AvailableDevice in UdpTransport::connect"),
         };
+        log::debug!("connecting to {}", device);
 
-        // TODO: change endpoint
-        let socket = UdpSocket::bind("127.0.0.1:34259")
-            .map_err(|err| Error::Udp(err))?;
+        let address = match device.debug {
+            false => transport.address(),
+            true => transport.debug_address(),
+        };
+        let socket = connect_socket(&address)?;
 
-        let read_timeout = Duration::from_millis(READ_TIMEOUT_MS);
-        let write_timeout = Duration::from_millis(WRITE_TIMEOUT_MS);
-        let _ = socket.set_read_timeout(Some(read_timeout));
-        let _ = socket.set_write_timeout(Some(write_timeout));
+        let config = TransportConfig::default();
+        let _ = socket.set_write_timeout(Some(config.device_timeout));
+
+        let socket = Arc::new(socket);
 
         Ok(Box::new(UdpTransport {
-            protocol: ProtocolV1 {
-                link: UdpLink {
-                    socket,
-                    address: transport.address(),
-                },
-            },
+            protocol: NegotiatedProtocol::new(UdpLink {
+                io: IoThread::spawn(read_attempt(socket.clone()))?,
+                socket,
+                read_timeout: config.read_timeout(),
+            }),
         }))
     }
 }
@@ -107,4 +207,123 @@ impl super::Transport for UdpTransport {
     async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
         self.protocol.read().await
     }
+
+    fn set_config(&mut self, config: &TransportConfig) {
+        self.protocol.set_probe_v2(config.protocol_v2);
+        let link = self.protocol.link_mut();
+        link.read_timeout = config.read_timeout();
+        let _ = link.socket.set_write_timeout(Some(config.device_timeout));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::trezor_with_transport;
+    use crate::protos::MessageType;
+    use crate::transport::v1_chunks;
+    use futures::executor::block_on;
+    use futures::future;
+    use std::thread;
+
+    /// Bind the sockets of an emulator and its debug link on two consecutive ports.
+    fn bind_pair() -> (UdpSocket, UdpSocket) {
+        loop {
+            let main = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let port = main.local_addr().unwrap().port();
+            if port == u16::MAX {
+                continue;
+            }
+            // Another socket may hold the following port, then try another pair.
+            if let Ok(debug) = UdpSocket::bind(("127.0.0.1", port + 1)) {
+                return (main, debug);
+            }
+        }
+    }
+
+    /// Answer one ping on the socket.
+    fn answer_ping(socket: UdpSocket) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let mut buf = [0; 8];
+            let (n, peer) = socket.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[..n], PING_DATAGRAM);
+            socket.send_to(PONG_DATAGRAM, peer).unwrap();
+        })
+    }
+
+    fn port_of(socket: &UdpSocket) -> usize {
+        socket.local_addr().unwrap().port() as usize
+    }
+
+    #[test]
+    fn test_find_devices() {
+        let (main, debug) = bind_pair();
+        let port = port_of(&main);
+        let emulator = answer_ping(main);
+        // The debug socket is bound, but doesn't answer.
+        let devices = UdpTransport::find_devices(DEFAULT_HOST, port).unwrap();
+        emulator.join().unwrap();
+        drop(debug);
+
+        assert_eq!(devices.len(), 1);
+        assert!(!devices[0].debug);
+
+        let (main, debug) = bind_pair();
+        let port = port_of(&main);
+        let emulators = [answer_ping(main), answer_ping(debug)];
+        let devices = UdpTransport::find_devices(DEFAULT_HOST, port).unwrap();
+        for emulator in emulators {
+            emulator.join().unwrap();
+        }
+
+        assert_eq!(devices.len(), 2);
+        assert!(!devices[0].debug && devices[1].debug);
+    }
+
+    #[test]
+    fn test_parallel_connections() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let device = AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: DEFAULT_HOST.to_owned(),
+                port: port_of(&socket),
+            }),
+        };
+
+        // Only answer once both connections have sent their ping, so the pings must be
+        // in flight at the same time.
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let emulator = thread::spawn(move || {
+            let mut peers = Vec::new();
+            while peers.len() < 2 {
+                let mut chunk = [0; CHUNK_SIZE];
+                let (_, peer) = socket.recv_from(&mut chunk).unwrap();
+                assert_eq!(&chunk[3..5], &[0x00, MessageType::MessageType_Ping as u8]);
+                peers.push(peer);
+            }
+            assert_ne!(peers[0], peers[1]);
+            let success = ProtoMessage(MessageType::MessageType_Success, vec![]);
+            for peer in peers {
+                for chunk in v1_chunks(success.clone()) {
+                    socket.send_to(&chunk, peer).unwrap();
+                }
+            }
+        });
+
+        let connect = || {
+            let transport = UdpTransport::connect(&device).unwrap();
+            trezor_with_transport(TrezorModel::T, transport)
+        };
+        let (mut first, mut second) = (connect(), connect());
+        let (a, b) = block_on(future::join(
+            async { first.ping("first").await?.ok() },
+            async { second.ping("second").await?.ok() },
+        ));
+        emulator.join().unwrap();
+        a.unwrap();
+        b.unwrap();
+    }
 }
@@ -1,10 +1,14 @@
 use async_trait::async_trait;
 use rusb::UsbContext;
 use std::fmt;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{TrezorModel, AvailableDevice};
-use super::{Error, Transport, AvailableDeviceTransport, Link, Protocol, ProtocolV1, ProtoMessage};
+use crate::{DeviceMode, TrezorModel, AvailableDevice};
+use super::{Error, Transport, TransportConfig, AvailableDeviceTransport, Link, Protocol, NegotiatedProtocol, ProtoMessage};
+use super::blocking::IoThread;
 
 pub const CONFIG_ID: u8 = 0;
 pub const INTERFACE_DESCRIPTOR: u8 = 0;
@@ -19,14 +23,14 @@ pub const READ_ENDPOINT_MASK: u8 = 0x80;
 /// The chunk size for the serial protocol.
 const CHUNK_SIZE: usize = 64;
 
-const READ_TIMEOUT_MS: u64 = 100000;
-const WRITE_TIMEOUT_MS: u64 = 100000;
 
 /// An available transport for connecting with a device.
 #[derive(Debug)]
 pub struct AvailableUsbTransport {
     pub bus: u8,
     pub address: u8,
+    /// The USB serial number, if the device could be opened to read it.
+    pub serial_number: Option<String>,
 }
 
 impl fmt::Display for AvailableUsbTransport {
@@ -40,23 +44,55 @@ impl From<rusb::Device<rusb::Context>> for AvailableUsbTransport {
         Self {
             bus: dev.bus_number(),
             address: dev.address(),
+            serial_number: None,
         }
     }
 }
 
 /// An actual serial HID USB link to a device over which bytes can be sent.
+///
+/// The device handle keeps its libusb context alive, and releases the claimed
+/// interface and closes the device when the last reference is dropped. The
+/// transfers run on the I/O thread of the link, which shares the handle.
 pub struct UsbLink {
-    libusb_context: &'static rusb::Context,
-    handle: &'static mut rusb::DeviceHandle<rusb::Context>,
+    handle: Arc<rusb::DeviceHandle<rusb::Context>>,
     endpoint: u8,
+    io: IoThread,
+    read_timeout: Duration,
+    write_timeout: Duration,
 }
 
-impl Drop for UsbLink {
-    fn drop(&mut self) {
-        // Re-box the two static references and manually drop them.
-        drop(unsafe { Box::from_raw(self.handle) });
-        let context_ptr = self.libusb_context as *const _ as *mut rusb::Context;
-        drop(unsafe { Box::from_raw(context_ptr) });
+impl UsbLink {
+    fn new(
+        handle: rusb::DeviceHandle<rusb::Context>,
+        endpoint: u8,
+        config: &TransportConfig,
+    ) -> Result<UsbLink, Error> {
+        let handle = Arc::new(handle);
+        Ok(UsbLink {
+            io: IoThread::spawn(read_attempt(handle.clone(), endpoint))?,
+            handle,
+            endpoint,
+            read_timeout: config.read_timeout(),
+            write_timeout: config.device_timeout,
+        })
+    }
+}
+
+/// A read attempt for the I/O thread.
+fn read_attempt(
+    handle: Arc<rusb::DeviceHandle<rusb::Context>>,
+    endpoint: u8,
+) -> impl FnMut(Duration) -> Result<Option<Vec<u8>>, Error> + Send + 'static {
+    let endpoint = READ_ENDPOINT_MASK | endpoint;
+    move |timeout| {
+        let mut chunk = vec![0; CHUNK_SIZE];
+        match handle.read_interrupt(endpoint, &mut chunk, timeout) {
+            Ok(CHUNK_SIZE) => Ok(Some(chunk)),
+            Ok(n) => Err(Error::UnexpectedChunkSizeFromDevice(n)),
+            Err(rusb::Error::Timeout) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
     }
 }
 
@@ -64,30 +100,29 @@ impl Drop for UsbLink {
 impl Link for UsbLink {
     async fn write_chunk(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
         debug_assert_eq!(CHUNK_SIZE, chunk.len());
-        let timeout = Duration::from_millis(WRITE_TIMEOUT_MS);
-        if let Err(e) = self.handle.write_interrupt(self.endpoint, &chunk, timeout) {
-            return Err(e)?;
-        }
-        Ok(())
+        let handle = self.handle.clone();
+        let endpoint = self.endpoint;
+        let timeout = self.write_timeout;
+        self.io
+            .run(move || {
+                handle.write_interrupt(endpoint, &chunk, timeout)?;
+                Ok(())
+            })
+            .await
     }
 
     async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
-        let mut chunk = vec![0; CHUNK_SIZE];
-        let endpoint = READ_ENDPOINT_MASK | self.endpoint;
-        let timeout = Duration::from_millis(READ_TIMEOUT_MS);
-
-        let n = self.handle.read_interrupt(endpoint, &mut chunk, timeout)?;
-        if n == CHUNK_SIZE {
-            Ok(chunk)
-        } else {
-            Err(Error::DeviceReadTimeout)
-        }
+        self.io.read(self.read_timeout).await
+    }
+
+    async fn read_chunk_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<u8>>, Error> {
+        self.io.read_timeout(self.read_timeout, timeout).await
     }
 }
 
 /// An implementation of the Transport interface for USB devices.
 pub struct UsbTransport {
-    protocol: ProtocolV1<UsbLink>,
+    protocol: NegotiatedProtocol<UsbLink>,
 }
 
 impl UsbTransport {
@@ -117,15 +152,24 @@ impl UsbTransport {
                 continue;
             }
 
+            // Opening fails f.e. without permissions, the device is still listed then.
+            let serial_number = dev
+                .open()
+                .ok()
+                .and_then(|handle| handle.read_serial_number_string_ascii(&desc).ok());
+
             devices.push(AvailableDevice {
                 model,
+                mode: DeviceMode::from_device_descriptor(&desc),
                 debug: false,
                 transport: AvailableDeviceTransport::Usb(AvailableUsbTransport {
                     bus: dev.bus_number(),
                     address: dev.address(),
+                    serial_number,
                 }),
             });
         }
+        log::trace!("found {} devices over USB", devices.len());
         Ok(devices)
     }
 
@@ -135,50 +179,38 @@ impl UsbTransport {
             AvailableDeviceTransport::Usb(ref t) => t,
             _ => panic!("passed wrong AvailableDevice in UsbTransport::connect"),
         };
+        log::debug!("connecting to {}", device);
 
         let interface = match device.debug {
             false => INTERFACE,
             true => INTERFACE_DEBUG,
         };
 
-        // To circumvent a limitation from the libusb crate, we need to do some unsafe stuff to be
-        // able to store the context and the device handle.  We will allocate them on the heap using
-        // boxes, but leak them into static references. In the Drop method for the Transport, we
-        // will release the memory manually.
-
         let context = rusb::Context::new()?;
-        let context_ptr = Box::into_raw(Box::new(context));
-        let context_ref = unsafe { &*context_ptr as &'static rusb::Context };
         // Go over the devices again to match the desired device.
-        let handle = {
-            let dev = context_ref
-                .devices()?
-                .iter()
-                .find(|dev| dev.bus_number() == transport.bus && dev.address() == transport.address)
-                .ok_or(Error::DeviceDisconnected)?;
-            // Check if there is not another device connected on this bus.
-            let dev_desc = dev.device_descriptor()?;
-            if TrezorModel::from_device_descriptor(&dev_desc).as_ref() != Some(&device.model) {
-                return Err(Error::DeviceDisconnected);
-            }
-            let mut handle = dev.open()?;
-            handle.claim_interface(interface)?;
-            handle
+        let dev = context
+            .devices()?
+            .iter()
+            .find(|dev| dev.bus_number() == transport.bus && dev.address() == transport.address)
+            .ok_or(Error::DeviceDisconnected)?;
+        // Check if there is not another device connected on this bus.
+        let dev_desc = dev.device_descriptor()?;
+        if TrezorModel::from_device_descriptor(&dev_desc).as_ref() != Some(&device.model) {
+            return Err(Error::DeviceDisconnected);
+        }
+        let mut handle = dev.open()?;
+        handle.claim_interface(interface)?;
+        let endpoint = match device.debug {
+            false => ENDPOINT,
+            true => ENDPOINT_DEBUG,
         };
-        let handle_ptr = Box::into_raw(Box::new(handle));
-        let handle_ref = unsafe { &mut *handle_ptr as &'static mut rusb::DeviceHandle<rusb::Context> };
 
         Ok(Box::new(UsbTransport {
-            protocol: ProtocolV1 {
-                link: UsbLink {
-                    libusb_context: context_ref,
-                    handle: handle_ref,
-                    endpoint: match device.debug {
-                        false => ENDPOINT,
-                        true => ENDPOINT_DEBUG,
-                    },
-                },
-            },
+            protocol: NegotiatedProtocol::new(UsbLink::new(
+                handle,
+                endpoint,
+                &TransportConfig::default(),
+            )?),
         }))
     }
 }
@@ -198,4 +230,82 @@ impl super::Transport for UsbTransport {
     async fn read_message(&mut self) -> Result<ProtoMessage, Error> {
         self.protocol.read().await
     }
+
+    fn set_config(&mut self, config: &TransportConfig) {
+        self.protocol.set_probe_v2(config.protocol_v2);
+        let link = self.protocol.link_mut();
+        link.read_timeout = config.read_timeout();
+        link.write_timeout = config.device_timeout;
+    }
+}
+
+/// How often the devices are enumerated where libusb can't report the changes.
+const HOTPLUG_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Waits for USB devices to come and go.
+///
+/// Uses the libusb hotplug events where the platform supports them, and reports
+/// a possible change on every poll interval otherwise.
+pub(crate) struct UsbHotplug {
+    context: rusb::Context,
+    registration: Option<rusb::Registration<rusb::Context>>,
+    changed: Arc<AtomicBool>,
+    /// When the devices were last enumerated without hotplug events.
+    last_poll: Option<Instant>,
+}
+
+/// The hotplug callback. libusb forbids I/O from the callback, so it only flags
+/// the change and the devices are enumerated afterwards.
+struct HotplugFlag(Arc<AtomicBool>);
+
+impl rusb::Hotplug<rusb::Context> for HotplugFlag {
+    fn device_arrived(&mut self, _device: rusb::Device<rusb::Context>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    fn device_left(&mut self, _device: rusb::Device<rusb::Context>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+impl UsbHotplug {
+    pub(crate) fn new() -> Result<UsbHotplug, Error> {
+        let context = rusb::Context::new()?;
+        let changed = Arc::new(AtomicBool::new(false));
+        let registration = match rusb::has_hotplug() {
+            true => Some(context.register_callback(
+                None,
+                None,
+                None,
+                Box::new(HotplugFlag(changed.clone())),
+            )?),
+            false => None,
+        };
+        Ok(UsbHotplug {
+            context,
+            registration,
+            changed,
+            last_poll: None,
+        })
+    }
+
+    /// Wait up to `timeout` for a change, returns whether the devices may have changed.
+    pub(crate) fn wait(&mut self, timeout: Duration) -> Result<bool, Error> {
+        if self.registration.is_none() {
+            let interval = Duration::from_millis(HOTPLUG_POLL_INTERVAL_MS);
+            let wait = match self.last_poll {
+                Some(last_poll) => interval.checked_sub(last_poll.elapsed()).unwrap_or_default(),
+                None => Duration::default(),
+            };
+            if wait > timeout {
+                thread::sleep(timeout);
+                return Ok(false);
+            }
+            thread::sleep(wait);
+            self.last_poll = Some(Instant::now());
+            return Ok(true);
+        }
+        self.context.handle_events(Some(timeout))?;
+        Ok(self.changed.swap(false, Ordering::SeqCst))
+    }
 }
@@ -1,11 +1,11 @@
 use crate::transport::{
     AvailableDeviceTransport, Error, Link, ProtoMessage, Protocol, ProtocolV1, Transport,
 };
-use crate::{AvailableDevice, TrezorModel};
+use crate::{AvailableDevice, DeviceMode, TrezorModel};
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, StreamExt};
-use js_sys::{Array, ArrayBuffer, Promise, Uint8Array};
+use js_sys::{Array, ArrayBuffer, Function, Promise, Uint8Array};
 use std::fmt;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -54,6 +54,11 @@ enum WebUsbAction {
         path: String,
         result_tx: ActionResultSender<Vec<u8>>,
     },
+    Listen {
+        changes_tx: mpsc::UnboundedSender<()>,
+        result_tx: ActionResultSender<()>,
+    },
+    Unlisten,
 }
 
 async fn init_webusb_plugin() -> Result<ActionSender, Error> {
@@ -62,6 +67,8 @@ async fn init_webusb_plugin() -> Result<ActionSender, Error> {
         .map_err(|e| Error::WebUsb(format!("Error initializing WebUSB: {:?}", e)))?;
 
     let fut = async move {
+        // The callback of the device listeners, it must live as long as they are registered.
+        let mut _listener = None;
         while let Some(action) = rx.next().await {
             match action {
                 WebUsbAction::RequestDevice { result_tx } => {
@@ -82,6 +89,19 @@ async fn init_webusb_plugin() -> Result<ActionSender, Error> {
                 WebUsbAction::ReadChunk { path, result_tx } => {
                     result_tx.send(on_read_chunk(&plugin, path).await).ok();
                 }
+                WebUsbAction::Listen {
+                    changes_tx,
+                    result_tx,
+                } => {
+                    let res = on_listen(&plugin, changes_tx).map(|callback| {
+                        _listener = Some(callback);
+                    });
+                    result_tx.send(res).ok();
+                }
+                WebUsbAction::Unlisten => {
+                    plugin.unlisten();
+                    _listener = None;
+                }
             }
         }
     };
@@ -133,7 +153,7 @@ async fn on_read_chunk(plugin: &TrezorWebUsbPlugin, path: String) -> TransportRe
         .map_err(|data| Error::WebUsb(format!("Expected 'ArrayBuffer', found: {:?}", data)))?;
     let buf = Uint8Array::new(&buf);
     let chunk = buf.to_vec();
-    crate::console_log!("Received chunk: {:?}", chunk);
+    log::trace!("read a chunk of {} bytes from {}", chunk.len(), path);
     // if chunk.len() != CHUNK_SIZE {
     //     return Err(Error::WebUsb(format!(
     //         "Received an invalid chunk: {:?}",
@@ -143,6 +163,19 @@ async fn on_read_chunk(plugin: &TrezorWebUsbPlugin, path: String) -> TransportRe
     Ok(chunk)
 }
 
+fn on_listen(
+    plugin: &TrezorWebUsbPlugin,
+    changes_tx: mpsc::UnboundedSender<()>,
+) -> TransportResult<Closure<dyn FnMut()>> {
+    let callback = Closure::wrap(Box::new(move || {
+        changes_tx.unbounded_send(()).ok();
+    }) as Box<dyn FnMut()>);
+    plugin
+        .listen(callback.as_ref().unchecked_ref())
+        .map_err(|e| Error::WebUsb(format!("Error listening for devices: {:?}", e)))?;
+    Ok(callback)
+}
+
 #[rustfmt::skip]
 #[wasm_bindgen(raw_module = "../../../js/trezor-webusb-plugin.js")]
 extern "C" {
@@ -184,11 +217,20 @@ extern "C" {
 
     #[wasm_bindgen(catch, method, js_name = requestDevice)]
     async fn request_device(this: &TrezorWebUsbPlugin) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch, method)]
+    fn listen(this: &TrezorWebUsbPlugin, callback: &Function) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn unlisten(this: &TrezorWebUsbPlugin);
 }
 
 pub struct WebUsbLink {
     event_tx: ActionSender,
     device: AvailableWebUsbDevice,
+    /// The read still running in the plugin after its caller stopped waiting, f.e.
+    /// on a timeout. Its chunk goes to the next read.
+    pending_read: Option<oneshot::Receiver<TransportResult<Vec<u8>>>>,
 }
 
 #[async_trait]
@@ -209,18 +251,34 @@ impl Link for WebUsbLink {
     }
 
     async fn read_chunk(&mut self) -> Result<Vec<u8>, Error> {
-        let (result_tx, result_rx) = oneshot::channel();
-        let path = self.device.path.clone();
-        send_event_recv_response(
-            &self.event_tx,
-            WebUsbAction::ReadChunk { path, result_tx },
-            result_rx,
-        )
-        .await
+        let event_tx = &self.event_tx;
+        let path = &self.device.path;
+        let result_rx = self.pending_read.get_or_insert_with(|| {
+            let (result_tx, result_rx) = oneshot::channel();
+            // If the plugin is gone, the sender is dropped and receiving fails below.
+            let path = path.clone();
+            event_tx
+                .unbounded_send(WebUsbAction::ReadChunk { path, result_tx })
+                .ok();
+            result_rx
+        });
+
+        let res = result_rx.await;
+        self.pending_read = None;
+        match res {
+            Ok(result) => result,
+            Err(e) => {
+                let error = format!("Error receiving result: {}", e);
+                Err(Error::Internal(error))
+            }
+        }
     }
 }
 
 /// An implementation of the Transport interface for WebUSB devices.
+///
+/// Always speaks [ProtocolV1]: WebUSB can't abort a pending transfer, so the protocol
+/// version can't be probed without losing the first chunk of the next response.
 pub struct WebUsbTransport {
     protocol: ProtocolV1<WebUsbLink>,
 }
@@ -246,12 +304,16 @@ impl Transport for WebUsbTransport {
 
 impl WebUsbTransport {
     pub async fn find_devices() -> Result<Vec<AvailableDevice>, Error> {
-        let mut event_sender = init_webusb_plugin().await?;
+        let event_sender = init_webusb_plugin().await?;
         WebUsbTransport::request_device(&event_sender).await?;
+        WebUsbTransport::list_devices(&event_sender).await
+    }
 
-        let (result_tx, mut result_rx) = oneshot::channel();
+    /// List the devices the page was granted access to, without asking for more.
+    async fn list_devices(event_sender: &ActionSender) -> Result<Vec<AvailableDevice>, Error> {
+        let (result_tx, result_rx) = oneshot::channel();
         send_event_recv_response(
-            &event_sender,
+            event_sender,
             WebUsbAction::FindDevices { result_tx },
             result_rx,
         )
@@ -259,19 +321,21 @@ impl WebUsbTransport {
         .map(|devices| {
             devices
                 .into_iter()
-                .map(|device| {
+                .filter_map(|device| {
                     let debug = device.debug;
+                    let model = TrezorModel::from_usb_ids(device.vendor_id, device.product_id)?;
+                    let mode = DeviceMode::from_usb_ids(device.vendor_id, device.product_id);
                     let webusb_transport = AvailableWebUsbTransport {
                         event_tx: event_sender.clone(),
                         device,
                     };
-                    let model = TrezorModel::T;
                     let transport = AvailableDeviceTransport::WebUsb(webusb_transport);
-                    AvailableDevice {
+                    Some(AvailableDevice {
                         model,
+                        mode,
                         debug,
                         transport,
-                    }
+                    })
                 })
                 .collect()
         })
@@ -283,13 +347,13 @@ impl WebUsbTransport {
             AvailableDeviceTransport::WebUsb(ref t) => t,
             _ => panic!("passed wrong AvailableDevice in WebUsbTransport::connect"),
         };
+        log::debug!("connecting to {}", device);
         Ok(Box::new(WebUsbTransport {
-            protocol: ProtocolV1 {
-                link: WebUsbLink {
-                    device: transport.device.clone(),
-                    event_tx: transport.event_tx.clone(),
-                },
-            },
+            protocol: ProtocolV1::new(WebUsbLink {
+                device: transport.device.clone(),
+                event_tx: transport.event_tx.clone(),
+                pending_read: None,
+            }),
         }))
     }
 
@@ -304,10 +368,59 @@ impl WebUsbTransport {
     }
 }
 
+/// Listens for the devices the page has access to being connected or disconnected.
+///
+/// The listeners are removed when it is dropped.
+pub(crate) struct WebUsbListener {
+    event_tx: ActionSender,
+    changes_rx: mpsc::UnboundedReceiver<()>,
+}
+
+impl WebUsbListener {
+    pub(crate) async fn new() -> Result<WebUsbListener, Error> {
+        let event_tx = init_webusb_plugin().await?;
+        let (changes_tx, changes_rx) = mpsc::unbounded();
+        let (result_tx, result_rx) = oneshot::channel();
+        send_event_recv_response(
+            &event_tx,
+            WebUsbAction::Listen {
+                changes_tx,
+                result_tx,
+            },
+            result_rx,
+        )
+        .await?;
+        Ok(WebUsbListener {
+            event_tx,
+            changes_rx,
+        })
+    }
+
+    /// List the devices the page has access to.
+    pub(crate) async fn list_devices(&self) -> Result<Vec<AvailableDevice>, Error> {
+        WebUsbTransport::list_devices(&self.event_tx).await
+    }
+
+    /// Wait for a device to be connected or disconnected.
+    pub(crate) async fn next_change(&mut self) -> Option<()> {
+        self.changes_rx.next().await
+    }
+}
+
+impl Drop for WebUsbListener {
+    fn drop(&mut self) {
+        self.event_tx.unbounded_send(WebUsbAction::Unlisten).ok();
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 struct AvailableWebUsbDevice {
     path: String,
     debug: bool,
+    #[serde(rename = "vendorId")]
+    vendor_id: u16,
+    #[serde(rename = "productId")]
+    product_id: u16,
 }
 
 #[derive(Debug)]
@@ -316,6 +429,13 @@ pub struct AvailableWebUsbTransport {
     event_tx: ActionSender,
 }
 
+impl AvailableWebUsbTransport {
+    /// The path of the device in the plugin, its serial number.
+    pub fn path(&self) -> &str {
+        &self.device.path
+    }
+}
+
 impl fmt::Display for AvailableWebUsbTransport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self.device)
@@ -0,0 +1,273 @@
+//! # Device hotplug
+//!
+//! [watch_devices] reports the devices as they are connected and disconnected. The
+//! devices are enumerated again on every change and told apart by
+//! [AvailableDevice::id].
+//!
+//! The devices without a USB serial number, like Trezor Model T, get a new id when
+//! they are plugged in again, so a replug shows as another device, see
+//! [DeviceEvent::has_stable_id].
+
+use futures::channel::mpsc;
+use futures::Stream;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{AvailableDevice, Error, Result};
+
+/// A change in the connected devices.
+#[derive(Debug)]
+pub enum DeviceEvent {
+    /// A device was connected, or was already connected when the watch started.
+    Connected(AvailableDevice),
+    /// The device was disconnected.
+    Disconnected {
+        /// The [AvailableDevice::id] of the device.
+        id: String,
+        /// See [AvailableDevice::has_stable_id].
+        stable_id: bool,
+    },
+}
+
+impl DeviceEvent {
+    /// Whether the id of the device stays the same when it is plugged in again.
+    ///
+    /// Otherwise the device may come back as another one, read `Features::device_id`
+    /// to recognize it.
+    pub fn has_stable_id(&self) -> bool {
+        match self {
+            DeviceEvent::Connected(device) => device.has_stable_id(),
+            DeviceEvent::Disconnected { stable_id, .. } => *stable_id,
+        }
+    }
+}
+
+/// A stream of [DeviceEvent]s, created by [watch_devices].
+///
+/// The devices are no longer watched once it is dropped.
+pub struct DeviceWatcher {
+    events_rx: mpsc::UnboundedReceiver<DeviceEvent>,
+    #[cfg(target_arch = "wasm32")]
+    _stop_tx: futures::channel::oneshot::Sender<()>,
+}
+
+impl Stream for DeviceWatcher {
+    type Item = DeviceEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<DeviceEvent>> {
+        Pin::new(&mut self.events_rx).poll_next(cx)
+    }
+}
+
+/// The devices seen so far, to turn the enumerations into events.
+#[derive(Default)]
+struct KnownDevices {
+    /// The ids of the devices and whether they are stable.
+    ids: HashMap<String, bool>,
+}
+
+impl KnownDevices {
+    /// Compare the enumerated devices with the known ones.
+    fn update(&mut self, devices: Vec<AvailableDevice>) -> Vec<DeviceEvent> {
+        let ids: HashMap<String, bool> = devices
+            .iter()
+            .map(|device| (device.id(), device.has_stable_id()))
+            .collect();
+        let mut events: Vec<DeviceEvent> = self
+            .ids
+            .iter()
+            .filter(|(id, _)| !ids.contains_key(*id))
+            .map(|(id, &stable_id)| DeviceEvent::Disconnected {
+                id: id.clone(),
+                stable_id,
+            })
+            .collect();
+        events.extend(
+            devices
+                .into_iter()
+                .filter(|device| !self.ids.contains_key(&device.id()))
+                .map(DeviceEvent::Connected),
+        );
+        self.ids = ids;
+        events
+    }
+
+    /// Send the changes, returns `false` if the watcher was dropped.
+    fn send_changes(
+        &mut self,
+        devices: Vec<AvailableDevice>,
+        events_tx: &mpsc::UnboundedSender<DeviceEvent>,
+    ) -> bool {
+        self.update(devices)
+            .into_iter()
+            .all(|event| events_tx.unbounded_send(event).is_ok())
+    }
+}
+
+/// Watch for Trezor devices being connected and disconnected.
+///
+/// The devices connected at the start are reported first. libusb reports the changes
+/// where the platform supports hotplug, otherwise the devices are polled.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn watch_devices() -> Result<DeviceWatcher> {
+    use crate::transport::usb::UsbHotplug;
+    use futures::executor::block_on;
+    use std::thread;
+    use std::time::Duration;
+
+    /// How often the thread checks whether the watcher was dropped.
+    const WATCH_INTERVAL_MS: u64 = 200;
+
+    let mut hotplug = UsbHotplug::new().map_err(Error::TransportConnect)?;
+    let (events_tx, events_rx) = mpsc::unbounded();
+    thread::spawn(move || {
+        let interval = Duration::from_millis(WATCH_INTERVAL_MS);
+        let mut known = KnownDevices::default();
+        let mut changed = true;
+        while !events_tx.is_closed() {
+            if changed {
+                // Devices may be half-enumerated while they come and go, so errors are not fatal.
+                if let Ok(devices) = block_on(crate::find_devices()) {
+                    if !known.send_changes(devices, &events_tx) {
+                        return;
+                    }
+                }
+            }
+            changed = hotplug.wait(interval).unwrap_or_else(|_| {
+                thread::sleep(interval);
+                true
+            });
+        }
+    });
+    Ok(DeviceWatcher { events_rx })
+}
+
+/// Watch for Trezor devices being connected and disconnected.
+///
+/// The devices connected at the start are reported first. Only the devices the page
+/// was granted access to, f.e. through [crate::find_devices], are reported.
+#[cfg(target_arch = "wasm32")]
+pub async fn watch_devices() -> Result<DeviceWatcher> {
+    use crate::transport::webusb::WebUsbListener;
+    use futures::channel::oneshot;
+    use futures::future::{self, Either};
+    use wasm_bindgen_futures::spawn_local;
+
+    let mut listener = WebUsbListener::new()
+        .await
+        .map_err(Error::TransportConnect)?;
+    let (events_tx, events_rx) = mpsc::unbounded();
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+    spawn_local(async move {
+        let mut known = KnownDevices::default();
+        loop {
+            // Devices may be half-enumerated while they come and go, so errors are not fatal.
+            if let Ok(devices) = listener.list_devices().await {
+                if !known.send_changes(devices, &events_tx) {
+                    return;
+                }
+            }
+            // The stop sender is dropped along with the watcher.
+            match future::select(Box::pin(listener.next_change()), &mut stop_rx).await {
+                Either::Left((Some(()), _)) => continue,
+                _ => return,
+            }
+        }
+    });
+    Ok(DeviceWatcher {
+        events_rx,
+        _stop_tx: stop_tx,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::udp::AvailableUdpTransport;
+    use crate::transport::usb::AvailableUsbTransport;
+    use crate::transport::AvailableDeviceTransport;
+    use crate::{DeviceMode, TrezorModel};
+
+    fn udp_device(port: usize, debug: bool) -> AvailableDevice {
+        AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug,
+            transport: AvailableDeviceTransport::Udp(AvailableUdpTransport {
+                host: "127.0.0.1".to_owned(),
+                port,
+            }),
+        }
+    }
+
+    fn ids(events: &[DeviceEvent]) -> Vec<String> {
+        let mut ids: Vec<String> = events
+            .iter()
+            .map(|event| match event {
+                DeviceEvent::Connected(device) => format!("+{}", device.id()),
+                DeviceEvent::Disconnected { id, .. } => format!("-{}", id),
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_known_devices() {
+        let mut known = KnownDevices::default();
+
+        let events = known.update(vec![udp_device(1, false), udp_device(1, true)]);
+        assert_eq!(
+            ids(&events),
+            vec!["+udp:127.0.0.1:1", "+udp:127.0.0.1:1/debug"]
+        );
+
+        // Nothing changed.
+        let events = known.update(vec![udp_device(1, false), udp_device(1, true)]);
+        assert!(events.is_empty());
+
+        let events = known.update(vec![udp_device(2, false)]);
+        assert_eq!(
+            ids(&events),
+            vec![
+                "+udp:127.0.0.1:2",
+                "-udp:127.0.0.1:1",
+                "-udp:127.0.0.1:1/debug"
+            ]
+        );
+        // The emulators are identified by their address.
+        assert!(events.iter().all(DeviceEvent::has_stable_id));
+
+        // Trezor Model T reports a serial number of zeros.
+        let model_t = AvailableDevice {
+            model: TrezorModel::T,
+            mode: DeviceMode::Normal,
+            debug: false,
+            transport: AvailableDeviceTransport::Usb(AvailableUsbTransport {
+                bus: 1,
+                address: 4,
+                serial_number: Some("000000000000000000000000".to_owned()),
+            }),
+        };
+        let events = known.update(vec![model_t]);
+        assert_eq!(ids(&events), vec!["+usb:1:4", "-udp:127.0.0.1:2"]);
+        let connected = events
+            .iter()
+            .find(|event| matches!(event, DeviceEvent::Connected(_)))
+            .unwrap();
+        assert!(!connected.has_stable_id());
+        let events = known.update(vec![]);
+        assert!(!events[0].has_stable_id());
+    }
+
+    #[test]
+    fn test_send_changes_stops_without_watcher() {
+        let (events_tx, events_rx) = mpsc::unbounded();
+        let mut known = KnownDevices::default();
+        assert!(known.send_changes(vec![udp_device(1, false)], &events_tx));
+
+        drop(events_rx);
+        assert!(!known.send_changes(vec![udp_device(2, false)], &events_tx));
+    }
+}